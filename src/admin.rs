@@ -0,0 +1,256 @@
+use std::collections::VecDeque;
+use std::collections::hash_map::RandomState;
+use std::fmt::{Debug, Write as _};
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::daemon::spawn_periodic;
+use crate::{DaemonHandle, DualCache};
+
+// -----------------------------------------------------------------------------
+// HTTP Admin Endpoint (feature = "admin", adapter: feature = "axum")
+// -----------------------------------------------------------------------------
+
+/// A JSON reply of `AdminHandler::handle`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdminResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl AdminResponse {
+    pub const CONTENT_TYPE: &'static str = "application/json";
+
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+}
+
+/// Membrane position at one sample of the evict_point history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EvictPointSample {
+    /// Cache clock (milliseconds) when sampled.
+    pub at: u64,
+    pub evict_point: usize,
+    pub generation: u64,
+}
+
+/// Operator endpoint as a plain function from (method, path) to a JSON
+/// response, so it mounts in any HTTP server; `router` adapts it to axum.
+/// Paths are relative to wherever it is mounted:
+///
+/// - `GET /stats`: `CacheStats` plus `hit_ratio`, `evict_point` and `generation`
+/// - `GET /hottest?n=20`: top-ranked keys (rendered with `Debug`), counters and ranks
+/// - `GET /evict_point`: current membrane position and its sampled history
+/// - `POST /commit`, `POST /maintenance`: run the step now
+///
+/// All reads come from the mirror. The history holds the last `history_len`
+/// samples, taken by `sample`, `spawn` and every `POST /maintenance`.
+pub struct AdminHandler<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V, S>>,
+    history: Mutex<VecDeque<EvictPointSample>>,
+    history_len: usize,
+}
+
+impl<K, V, S> AdminHandler<K, V, S>
+where
+    K: Hash + Eq + Clone + Debug + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Keeps the last 256 evict_point samples.
+    pub fn new(cache: Arc<DualCache<K, V, S>>) -> Self {
+        Self::with_history(cache, 256)
+    }
+
+    pub fn with_history(cache: Arc<DualCache<K, V, S>>, history_len: usize) -> Self {
+        Self {
+            cache,
+            history: Mutex::new(VecDeque::with_capacity(history_len.min(4_096))),
+            history_len,
+        }
+    }
+
+    pub fn cache(&self) -> &Arc<DualCache<K, V, S>> {
+        &self.cache
+    }
+
+    /// Records the current membrane position in the history.
+    pub fn sample(&self) {
+        if self.history_len == 0 {
+            return;
+        }
+        let (at, evict_point, generation) = {
+            let snapshot = self.cache.mirror.load();
+            (snapshot.current_time(), snapshot.evict_point, snapshot.generation)
+        };
+        let mut history = self.history.lock();
+        if history.len() == self.history_len {
+            history.pop_front();
+        }
+        history.push_back(EvictPointSample { at, evict_point, generation });
+    }
+
+    /// Oldest sample first.
+    pub fn evict_point_history(&self) -> Vec<EvictPointSample> {
+        self.history.lock().iter().copied().collect()
+    }
+
+    /// Samples the evict_point every `interval` on a background thread.
+    pub fn spawn(self: &Arc<Self>, interval: Duration) -> DaemonHandle {
+        let this = Arc::clone(self);
+        spawn_periodic("dualcache-admin", interval, move || {
+            this.sample();
+            true
+        })
+    }
+
+    /// Answers one request. `path` may carry a query string.
+    pub fn handle(&self, method: &str, path: &str) -> AdminResponse {
+        let (route, query) = path.split_once('?').unwrap_or((path, ""));
+        let route = route.trim_end_matches('/');
+        let get = method.eq_ignore_ascii_case("GET");
+        let post = method.eq_ignore_ascii_case("POST");
+        match route {
+            "/stats" if get => AdminResponse::ok(self.stats_json()),
+            "/hottest" if get => match query_param(query, "n").map(str::parse::<usize>) {
+                None => AdminResponse::ok(self.hottest_json(20)),
+                Some(Ok(n)) => AdminResponse::ok(self.hottest_json(n)),
+                Some(Err(_)) => AdminResponse::error(400, "n must be a non-negative integer"),
+            },
+            "/evict_point" if get => AdminResponse::ok(self.evict_point_json()),
+            "/commit" if post => {
+                let generation = self.cache.commit();
+                AdminResponse::ok(format!("{{\"generation\":{generation}}}"))
+            }
+            "/maintenance" if post => {
+                self.cache.maintenance();
+                self.sample();
+                AdminResponse::ok(format!("{{\"evict_point\":{}}}", self.cache.main.lock().evict_point))
+            }
+            "/stats" | "/hottest" | "/evict_point" | "/commit" | "/maintenance" => {
+                AdminResponse::error(405, "method not allowed")
+            }
+            _ => AdminResponse::error(404, "not found"),
+        }
+    }
+
+    fn stats_json(&self) -> String {
+        let stats = self.cache.stats();
+        format!(
+            "{{\"hits\":{},\"misses\":{},\"hit_ratio\":{},\"insertions\":{},\"evictions\":{},\
+             \"expirations\":{},\"rejections\":{},\"dropped_signals\":{},\"channel_len\":{},\
+             \"channel_capacity\":{},\"occupancy\":{},\"capacity\":{},\"total_weight\":{},\
+             \"evict_point\":{},\"generation\":{}}}",
+            stats.hits,
+            stats.misses,
+            stats.hit_ratio(),
+            stats.insertions,
+            stats.evictions,
+            stats.expirations,
+            stats.rejections,
+            stats.dropped_signals,
+            stats.channel_len,
+            stats.channel_capacity,
+            stats.occupancy,
+            stats.capacity,
+            stats.total_weight,
+            self.cache.evict_point(),
+            self.cache.current_generation(),
+        )
+    }
+
+    fn hottest_json(&self, n: usize) -> String {
+        let mut out = String::from("[");
+        for (i, (key, counter, rank)) in self.cache.hottest(n).into_iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let key = json_string(&format!("{key:?}"));
+            let _ = write!(out, "{{\"key\":{key},\"counter\":{counter},\"rank\":{rank}}}");
+        }
+        out.push(']');
+        out
+    }
+
+    fn evict_point_json(&self) -> String {
+        let mut out = format!("{{\"evict_point\":{},\"history\":[", self.cache.evict_point());
+        for (i, sample) in self.history.lock().iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"at\":{},\"evict_point\":{},\"generation\":{}}}",
+                sample.at, sample.evict_point, sample.generation
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+// Helper: Value of `name` in a query string (no percent-decoding needed for ours)
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+// Helper: `text` as a quoted JSON string
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(ch));
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Mounts `handler` as an axum `Router` (a tower `Service`); nest it under a
+/// prefix such as `/admin`.
+#[cfg(feature = "axum")]
+pub fn router<K, V, S>(handler: Arc<AdminHandler<K, V, S>>) -> axum::Router
+where
+    K: Hash + Eq + Clone + Debug + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    use axum::http::{Method, StatusCode, Uri, header};
+    use axum::response::IntoResponse;
+
+    axum::Router::new().fallback(move |method: Method, uri: Uri| {
+        let handler = Arc::clone(&handler);
+        async move {
+            let path = uri.path_and_query().map_or(uri.path(), |path| path.as_str());
+            let response = handler.handle(method.as_str(), path);
+            let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (status, [(header::CONTENT_TYPE, AdminResponse::CONTENT_TYPE)], response.body).into_response()
+        }
+    })
+}
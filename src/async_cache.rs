@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossbeam::channel::Receiver;
+use parking_lot::Mutex;
+use tokio::sync::{Notify, OnceCell};
+use tokio::task::JoinHandle;
+
+use crate::time::Instant;
+use crate::{DaemonConfig, DualCache, Signal};
+
+// -----------------------------------------------------------------------------
+// Async Support (feature = "async")
+// -----------------------------------------------------------------------------
+
+/// In-flight async loads keyed by cache key, so concurrent misses share one future.
+pub(crate) type InFlight<K, V> = Mutex<HashMap<K, Arc<OnceCell<V>>>>;
+
+/// Handle to a daemon running as a tokio task.
+/// The task also exits on its own once the last `Arc<DualCache>` is dropped.
+pub struct AsyncDaemonHandle {
+    stop: Arc<Notify>,
+    close: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+
+impl AsyncDaemonHandle {
+    /// Stops the task after its current tick and waits for it to finish.
+    pub async fn shutdown(self) {
+        self.stop.notify_one();
+        let _ = self.task.await;
+    }
+
+    /// Graceful stop: queued signals and commands are applied and a final
+    /// commit is published before the task finishes.
+    pub async fn close(self) {
+        self.close.store(true, Ordering::Release);
+        self.shutdown().await;
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Runs the Daemon as a tokio task instead of a dedicated thread.
+    /// The task wakes every `commit_interval`, drains up to `max_batch`
+    /// signals without blocking (the crossbeam channel has no async receive,
+    /// so it is polled once per tick), applies them as one batch, and runs
+    /// maintenance/commit when due.
+    /// Like the thread daemon, the task only holds a `Weak` reference.
+    pub fn spawn_daemon_async(
+        self: &Arc<Self>,
+        rx: Receiver<Signal<K>>,
+        config: DaemonConfig,
+    ) -> AsyncDaemonHandle {
+        let stop = Arc::new(Notify::new());
+        let close = Arc::new(AtomicBool::new(false));
+        let weak = Arc::downgrade(self);
+        let stop_signal = Arc::clone(&stop);
+        let close_flag = Arc::clone(&close);
+
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.commit_interval);
+            let mut next_maintenance = Instant::now() + config.maintenance_interval;
+
+            loop {
+                tokio::select! {
+                    _ = stop_signal.notified() => break,
+                    _ = ticker.tick() => {}
+                }
+                let Some(cache) = weak.upgrade() else { return };
+
+                // Main-lock sections are short; nothing here awaits while locked
+                #[cfg(feature = "tracing")]
+                let _batch = tracing::trace_span!("daemon_batch").entered();
+                cache.process_signals(rx.try_iter().take(config.max_batch));
+                cache.apply_commands(cache.command_rx.try_iter().take(config.max_batch));
+                if Instant::now() >= next_maintenance {
+                    cache.maintenance();
+                    next_maintenance = Instant::now() + config.maintenance_interval;
+                }
+                cache.flush_read_signals();
+                cache.commit();
+                #[cfg(feature = "metrics")]
+                cache.export_metrics();
+            }
+
+            if close_flag.load(Ordering::Acquire)
+                && let Some(cache) = weak.upgrade()
+            {
+                cache.drain_and_commit(&rx);
+            }
+        });
+
+        AsyncDaemonHandle { stop, close, task }
+    }
+
+    /// Async counterpart of `wait_for_generation`.
+    pub async fn wait_for_generation_async(&self, generation: u64) {
+        loop {
+            // Register interest before checking, so a commit in between is not missed
+            let notified = self.commit_notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if *self.committed.lock() >= generation {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Async counterpart of `get_or_insert_with`.
+    /// The main lock is never held across an `.await`: concurrent misses for
+    /// the same key wait on one shared load, and only the final insert locks.
+    pub async fn get_or_insert_with_async<F, Fut>(&self, key: K, ttl_secs: u64, init: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        let cell = self
+            .in_flight
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone();
+
+        let value = cell
+            .get_or_init(|| async {
+                // Another writer may have inserted since the mirror was read
+                {
+                    let guard = self.main.lock();
+                    if let Some(idx) = guard.position(&key) {
+                        return guard.arena[idx].value.clone();
+                    }
+                }
+                let value = init().await;
+                self.write(|cache| cache.gatsby_insert(key.clone(), value.clone(), ttl_secs));
+                value
+            })
+            .await
+            .clone();
+
+        // Retire the in-flight slot unless a newer load already replaced it
+        let mut in_flight = self.in_flight.lock();
+        if in_flight.get(&key).is_some_and(|current| Arc::ptr_eq(current, &cell)) {
+            in_flight.remove(&key);
+        }
+        value
+    }
+}
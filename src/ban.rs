@@ -0,0 +1,87 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Quarantine (Temporary Key Bans)
+// -----------------------------------------------------------------------------
+
+// Banned keys and the clock millisecond their ban lapses
+pub(crate) type Bans<K> = Arc<Mutex<HashMap<K, u64>>>;
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Quarantines `key` for `duration`: its entry is deleted now and every
+    /// insert of it is rejected (counted in `rejections`) until the ban
+    /// lapses, e.g. to keep a poisoned upstream value from being re-cached.
+    /// Banning again replaces the deadline. Readers see the delete after the
+    /// next commit.
+    pub fn ban(&self, key: K, duration: Duration) {
+        self.write(|cache| {
+            let until = cache
+                .current_time()
+                .saturating_add(u64::try_from(duration.as_millis()).unwrap_or(u64::MAX));
+            cache.double_swap_delete(&key);
+            cache.bans.get_or_insert_with(Default::default).lock().insert(key, until);
+        });
+    }
+
+    /// Lifts a ban early. Returns false if `key` was not banned.
+    pub fn unban<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let cache = self.main.lock();
+        let now = cache.current_time();
+        cache
+            .bans
+            .as_ref()
+            .and_then(|bans| bans.lock().remove(key))
+            .is_some_and(|until| now <= until)
+    }
+
+    pub fn is_banned<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.main.lock().is_banned(key)
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Whether an unlapsed ban covers `key`
+    pub(crate) fn is_banned<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(bans) = &self.bans else { return false };
+        let now = self.current_time();
+        bans.lock().get(key).is_some_and(|&until| now <= until)
+    }
+
+    // Helper: Forgets lapsed bans
+    pub(crate) fn purge_bans(&mut self) {
+        let now = self.current_time();
+        if let Some(bans) = &self.bans {
+            bans.lock().retain(|_, until| now <= *until);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache, Node};
+
+// -----------------------------------------------------------------------------
+// Batch Writes (One Lock, One Commit)
+// -----------------------------------------------------------------------------
+
+/// Write access to `main` for the duration of a `DualCache::batch` closure.
+pub struct BatchWriter<'a, K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: &'a mut Cache<K, V, S>,
+    default_ttl: u64,
+    writes: u64,
+}
+
+impl<K, V, S> BatchWriter<'_, K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    pub fn insert(&mut self, key: K, value: V, ttl_secs: u64) {
+        self.cache.gatsby_insert(key, value, ttl_secs);
+        self.writes += 1;
+    }
+
+    /// Inserts with the builder's default TTL.
+    pub fn put(&mut self, key: K, value: V) {
+        self.insert(key, value, self.default_ttl);
+    }
+
+    pub fn update<Q>(&mut self, key: &Q, value: V)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.update_value(key, value);
+        self.writes += 1;
+    }
+
+    /// Returns the removed value, if the key was live.
+    pub fn delete<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.writes += 1;
+        self.cache.double_swap_delete(key).map(|node| node.value)
+    }
+
+    /// Reads the authoritative (uncommitted) state; does not signal a hit.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.position(key).map(|idx| &self.cache.arena[idx].value)
+    }
+
+    /// Operations applied so far.
+    pub fn writes(&self) -> u64 {
+        self.writes
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Applies all operations in `ops` under a single main-lock acquisition.
+    /// The batch counts as `writes()` writes against the `CommitPolicy`,
+    /// which is evaluated once at the end.
+    pub fn batch<R>(&self, ops: impl FnOnce(&mut BatchWriter<'_, K, V, S>) -> R) -> R {
+        self.run_batch(ops, false)
+    }
+
+    /// Like `batch`, but always commits once at the end so the whole batch
+    /// becomes visible to readers atomically.
+    pub fn batch_and_commit<R>(&self, ops: impl FnOnce(&mut BatchWriter<'_, K, V, S>) -> R) -> R {
+        self.run_batch(ops, true)
+    }
+
+    /// Bulk load: one lock, at most one cliff-edge truncation sized for the
+    /// whole batch, then one commit. A key repeated in `entries` keeps its
+    /// last value. When the batch holds more new keys than `capacity`, only
+    /// the last `capacity` of them are placed (the earlier ones would be
+    /// evicted by the later ones anyway).
+    pub fn insert_many<I>(&self, entries: I, ttl_secs: u64)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut guard = self.main.lock();
+        let nodes = entries
+            .into_iter()
+            .map(|(key, value)| guard.new_node(key, value, ttl_secs))
+            .collect();
+        guard.bulk_insert(nodes);
+        self.publish(&mut guard);
+    }
+
+    fn run_batch<R>(&self, ops: impl FnOnce(&mut BatchWriter<'_, K, V, S>) -> R, commit: bool) -> R {
+        let mut guard = self.main.lock();
+        let mut writer = BatchWriter {
+            cache: &mut guard,
+            default_ttl: self.default_ttl,
+            writes: 0,
+        };
+        let result = ops(&mut writer);
+        let writes = writer.writes;
+
+        if commit {
+            self.publish(&mut guard);
+        } else if writes > 0 {
+            self.after_writes(&mut guard, writes);
+        }
+        result
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Frees room for every new key of `nodes` with one truncation
+    // (at the membrane, or deeper if that is not enough), then places them
+    fn bulk_insert(&mut self, nodes: Vec<Node<K, V>>) {
+        // A key repeated within the batch is written once, with its last value
+        let last_write: Vec<bool> = {
+            let mut last = HashMap::with_capacity_and_hasher(nodes.len(), self.index.hasher().clone());
+            for (i, node) in nodes.iter().enumerate() {
+                last.insert(&node.key, i);
+            }
+            nodes.iter().enumerate().map(|(i, node)| last[&node.key] == i).collect()
+        };
+        let mut nodes: Vec<(Node<K, V>, bool)> = nodes
+            .into_iter()
+            .zip(last_write)
+            .filter(|&(_, last)| last)
+            .map(|(node, _)| {
+                let fresh = self.position(&node.key).is_none();
+                (node, fresh)
+            })
+            .collect();
+
+        let mut fresh = nodes.iter().filter(|&&(_, fresh)| fresh).count();
+        let mut surplus = fresh.saturating_sub(self.capacity);
+        if surplus > 0 {
+            nodes.retain(|&(_, new)| {
+                let keep = !new || surplus == 0;
+                surplus -= usize::from(!keep);
+                keep
+            });
+            fresh = self.capacity;
+        }
+
+        let room = self.capacity.saturating_sub(self.arena.len());
+        if fresh > room {
+            let cut = self.evict_point.min(self.capacity.saturating_sub(fresh));
+            self.truncate_at(cut);
+        }
+        for (node, _) in nodes {
+            self.gatsby_insert_node(node);
+        }
+    }
+}
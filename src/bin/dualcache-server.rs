@@ -0,0 +1,41 @@
+use std::net::TcpListener;
+use std::process;
+
+use ::DualCache::{DaemonConfig, DualCache, RespServer};
+
+// Standalone sidecar: `dualcache-server [--addr HOST:PORT] [--capacity N]`
+fn main() {
+    let mut addr = String::from("127.0.0.1:6380");
+    let mut capacity = 100_000;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next();
+        match (flag.as_str(), value) {
+            ("--addr", Some(value)) => addr = value,
+            ("--capacity", Some(value)) => match value.parse() {
+                Ok(value) => capacity = value,
+                Err(_) => usage(),
+            },
+            _ => usage(),
+        }
+    }
+
+    let (cache, rx) = DualCache::<Vec<u8>, Vec<u8>>::new(capacity);
+    let _daemon = cache.spawn_daemon(rx, DaemonConfig::default());
+
+    let listener = TcpListener::bind(&addr).unwrap_or_else(|err| {
+        eprintln!("dualcache-server: cannot bind {addr}: {err}");
+        process::exit(1);
+    });
+    eprintln!("dualcache-server: listening on {addr} (capacity {capacity})");
+    if let Err(err) = RespServer::new(cache).serve(listener) {
+        eprintln!("dualcache-server: {err}");
+        process::exit(1);
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: dualcache-server [--addr HOST:PORT] [--capacity N]");
+    process::exit(2);
+}
@@ -0,0 +1,188 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::hash::{BuildHasher, Hash};
+
+use cached::Cached;
+
+use crate::{Cache, DualCache, secs_to_millis};
+
+// -----------------------------------------------------------------------------
+// `cached` Crate Interop (feature = "cached")
+// -----------------------------------------------------------------------------
+
+/// `cached::Cached` for a `DualCache`, so it can back `#[cached]` functions
+/// (`ty = "DualCache<K, V>"`, creating it with `Arc::try_unwrap` on a freshly
+/// built cache) and code written against that trait.
+///
+/// The trait hands out references and takes `&mut self`, so it works on
+/// `main` directly (no lock, no mirror): hits climb right away, no Daemon
+/// needed, and every call counts in `stats`. Writes follow the
+/// `CommitPolicy` like any other. Entries live for the default TTL
+/// (`cache_lifespan`, settable with `cache_set_lifespan`), and
+/// `cache_get_or_set_with` always admits the value it computed.
+impl<K, V, S> Cached<K, V> for DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    fn cache_get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.live_slot(key)?;
+        Some(&self.main.get_mut().arena[idx].value)
+    }
+
+    fn cache_get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.live_slot(key)?;
+        let main = self.main.get_mut();
+        main.dirty = true;
+        Some(&mut main.arena[idx].value)
+    }
+
+    fn cache_set(&mut self, key: K, value: V) -> Option<V> {
+        let ttl_secs = self.default_ttl;
+        self.write(|cache| cache.replace_entry(key, value, ttl_secs))
+    }
+
+    fn cache_get_or_set_with<F: FnOnce() -> V>(&mut self, key: K, init: F) -> &mut V {
+        match self.cache_try_get_or_set_with(key, || Ok::<_, Infallible>(init())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// # Panics
+    /// On a zero-capacity cache, which cannot hold the value it returns.
+    fn cache_try_get_or_set_with<F: FnOnce() -> Result<V, E>, E>(
+        &mut self,
+        key: K,
+        init: F,
+    ) -> Result<&mut V, E> {
+        let idx = match self.live_slot(&key) {
+            Some(idx) => idx,
+            None => {
+                let value = init()?;
+                let ttl_secs = self.default_ttl;
+                self.write(|cache| {
+                    // The caller gets the value back by reference, so bypass admission
+                    let admission = cache.admission.take();
+                    cache.replace_entry(key.clone(), value, ttl_secs);
+                    cache.admission = admission;
+                });
+                self.main
+                    .get_mut()
+                    .position(&key)
+                    .expect("cache_get_or_set_with needs a non-zero capacity")
+            }
+        };
+        let main = self.main.get_mut();
+        main.dirty = true;
+        Ok(&mut main.arena[idx].value)
+    }
+
+    fn cache_remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            let now = cache.current_time();
+            cache
+                .double_swap_delete(key)
+                .filter(|node| !node.is_expired(now))
+                .map(|node| node.value)
+        })
+    }
+
+    fn cache_clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+
+    fn cache_reset(&mut self) {
+        self.cache_clear();
+    }
+
+    fn cache_reset_metrics(&mut self) {
+        self.counters.hits.take();
+        self.counters.misses.take();
+    }
+
+    fn cache_size(&self) -> usize {
+        self.main.lock().live_len()
+    }
+
+    fn cache_hits(&self) -> Option<u64> {
+        Some(self.counters.hits.get())
+    }
+
+    fn cache_misses(&self) -> Option<u64> {
+        Some(self.counters.misses.get())
+    }
+
+    fn cache_capacity(&self) -> Option<usize> {
+        Some(self.capacity())
+    }
+
+    fn cache_lifespan(&self) -> Option<u64> {
+        Some(self.default_ttl)
+    }
+
+    /// Sets the default TTL (also used by `put`) for later writes.
+    fn cache_set_lifespan(&mut self, seconds: u64) -> Option<u64> {
+        Some(std::mem::replace(&mut self.default_ttl, seconds))
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    // Helper: `key`'s live slot in `main` after counting the read and climbing
+    fn live_slot<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let main = self.main.get_mut();
+        let now = main.current_time();
+        let Some(idx) = main.position(key).filter(|&idx| !main.arena[idx].is_expired(now)) else {
+            self.counters.misses.incr();
+            return None;
+        };
+        let hit = main.arena[idx].key.clone();
+        main.viscous_climb(hit);
+        self.counters.hits.incr();
+        main.position(key)
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Inserts or replaces `key` with a fresh deadline; returns the
+    // previous live value
+    fn replace_entry(&mut self, key: K, value: V, ttl_secs: u64) -> Option<V> {
+        let Some(idx) = self.position(&key) else {
+            self.gatsby_insert(key, value, ttl_secs);
+            return None;
+        };
+        let now = self.current_time();
+        let node = &self.arena[idx];
+        let previous = (!node.is_expired(now)).then(|| node.value.clone());
+        self.move_deadline(idx, now.saturating_add(secs_to_millis(ttl_secs)));
+        self.update_value(&key, value);
+        previous
+    }
+}
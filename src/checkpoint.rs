@@ -0,0 +1,156 @@
+use std::collections::hash_map::RandomState;
+use std::fs::{self, File};
+use std::hash::{BuildHasher, Hash};
+use std::io::{BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crossbeam::channel::Receiver;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::daemon::spawn_periodic;
+use crate::{DaemonHandle, DualCache, DualCacheBuilder, Signal, SnapshotError};
+
+// -----------------------------------------------------------------------------
+// Checkpointing (Snapshot + Op Log Truncation, feature = "disk")
+// -----------------------------------------------------------------------------
+
+/// What the latest checkpoint captured, for monitoring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckpointInfo {
+    /// Last published generation when the checkpoint was cut. The checkpoint
+    /// holds `main`, so it also includes writes not yet committed.
+    pub generation: u64,
+    /// Live entries written.
+    pub entries: usize,
+    /// Cache clock (milliseconds) when the checkpoint was cut.
+    pub taken_at: u64,
+    /// Op log bytes the checkpoint made obsolete and removed.
+    pub log_bytes_discarded: u64,
+}
+
+/// Writes `main` to a snapshot file atomically (temp file, fsync, rename) and
+/// then drops the op log records the snapshot covers, so recovery
+/// (`DualCache::recover_checkpoint`) replays only what came after.
+///
+/// The snapshot is cut and the log end marked under one main lock, which
+/// is held for a structural clone only.
+pub struct Checkpointer<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V, S>>,
+    path: PathBuf,
+    last: Mutex<Option<CheckpointInfo>>,
+    failures: AtomicU64,
+}
+
+impl<K, V, S> Checkpointer<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    S: BuildHasher + Clone + Default + Send + Sync + 'static,
+{
+    /// Checkpoints `cache` to `path` (and truncates its op log, if one is set).
+    pub fn new(cache: Arc<DualCache<K, V, S>>, path: impl AsRef<Path>) -> Self {
+        Self {
+            cache,
+            path: path.as_ref().to_path_buf(),
+            last: Mutex::new(None),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Takes a checkpoint now.
+    pub fn checkpoint(&self) -> Result<CheckpointInfo, SnapshotError> {
+        let (snapshot, mark, taken_at) = {
+            let main = self.cache.main.lock();
+            let mark = main.oplog.as_ref().map(|log| log.mark()).transpose()?;
+            (main.clone(), mark, main.current_time())
+        };
+
+        // A crash before the rename leaves the previous checkpoint intact
+        let tmp_path = self.path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        snapshot.write_snapshot(&mut writer)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        drop(writer);
+        fs::rename(&tmp_path, &self.path)?;
+
+        // Only now is the log prefix redundant; a crash before this replays
+        // it over the checkpoint, which converges to the same state
+        let log_bytes_discarded = match (mark, &snapshot.oplog) {
+            (Some(mark), Some(log)) => log.discard_before(mark)?,
+            _ => 0,
+        };
+
+        let info = CheckpointInfo {
+            generation: snapshot.generation,
+            entries: snapshot.live_len(),
+            taken_at,
+            log_bytes_discarded,
+        };
+        *self.last.lock() = Some(info);
+        Ok(info)
+    }
+
+    /// The latest successful checkpoint, if any.
+    pub fn last_checkpoint(&self) -> Option<CheckpointInfo> {
+        *self.last.lock()
+    }
+
+    /// Checkpoints run by `spawn` that failed (the next run retries).
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Checkpoints every `interval` on a background thread.
+    /// `shutdown` on the handle takes one final checkpoint. The thread only
+    /// holds a `Weak` reference: after the `Checkpointer` is dropped (which
+    /// releases the cache), it exits at its next run.
+    pub fn spawn(self: &Arc<Self>, interval: Duration) -> DaemonHandle {
+        let this: Weak<Self> = Arc::downgrade(self);
+        spawn_periodic("dualcache-checkpoint", interval, move || {
+            let Some(this) = this.upgrade() else { return false };
+            if this.checkpoint().is_err() {
+                this.failures.fetch_add(1, Ordering::Relaxed);
+            }
+            true
+        })
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    S: BuildHasher + Clone + Default + Send + Sync + 'static,
+{
+    /// Loads the checkpoint at `checkpoint_path` (if present), replays the op
+    /// log at `log_path` on top of it and keeps appending to that log.
+    /// The recovered state is committed before returning.
+    pub fn recover_checkpoint(
+        checkpoint_path: impl AsRef<Path>,
+        log_path: impl AsRef<Path>,
+        capacity: usize,
+    ) -> Result<(Arc<Self>, Receiver<Signal<K>>), SnapshotError> {
+        let (cache, rx) = match File::open(checkpoint_path) {
+            Ok(file) => Self::load_snapshot(BufReader::new(file), capacity)?,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                DualCacheBuilder::new(capacity).build_with_hasher(S::default())
+            }
+            Err(err) => return Err(err.into()),
+        };
+        cache.replay_log(log_path.as_ref())?;
+        Ok((cache, rx))
+    }
+}
@@ -0,0 +1,84 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache, Garbage};
+
+// -----------------------------------------------------------------------------
+// Wiping the Cache
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Empties `main` (pinned entries included): arena, index and free list
+    /// are dropped, `counter_sum` and weights zeroed, and the evict point is
+    /// back where the builder put it. The cache stays wired to its channel
+    /// and Daemon; hit/miss statistics are kept. Listeners, subscribers and
+    /// the op log see a delete per live entry; key watches see their key
+    /// removed. The wipe is local: nothing is published on the invalidation
+    /// bus, so other nodes keep their replicas. Readers see the wipe after
+    /// the next commit. Returns the entry
+    /// count as `len` reported it (approximate: expired entries nobody
+    /// noticed yet are included).
+    pub fn clear(&self) -> usize {
+        self.write(Cache::clear)
+    }
+
+    /// `clear` and publish the empty cache under the same lock, so no reader
+    /// sees a state in between. Returns the generation published.
+    pub fn clear_and_commit(&self) -> u64 {
+        let mut guard = self.main.lock();
+        guard.clear();
+        self.publish(&mut guard)
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    pub(crate) fn clear(&mut self) -> usize {
+        let live = self.live_len();
+        #[cfg(feature = "disk")]
+        let logged = self.oplog.is_some();
+        #[cfg(not(feature = "disk"))]
+        let logged = false;
+        let observed = logged
+            || self.listener.is_some()
+            || self.events.is_some()
+            || self.watches.is_some();
+        if observed {
+            // Local only: the deletes below are not published to other nodes
+            let bus = self.invalidation.take();
+            let nodes = self.arena.split_off_segments(0);
+            for (idx, node) in nodes.iter().flat_map(|segment| segment.iter()).enumerate() {
+                if self.index.get(&node.key) == Some(&idx) {
+                    self.release_deleted(node);
+                }
+            }
+            self.discard(Garbage::Segments(nodes));
+            self.invalidation = bus;
+        } else {
+            self.drop_tail(0);
+        }
+
+        self.index.clear();
+        self.free.clear();
+        if let Some(wheel) = &self.expiry {
+            wheel.lock().clear();
+        }
+        self.counter_sum = 0;
+        self.total_weight = 0;
+        self.garbage = 0;
+        self.pinned_count = 0;
+        self.has_idle_entries = false;
+        self.has_priorities = false;
+        self.evict_point = self.base_evict_point.min(self.capacity);
+        self.dirty = true;
+        live
+    }
+}
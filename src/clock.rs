@@ -0,0 +1,113 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// -----------------------------------------------------------------------------
+// Time Sources (TTL / TTI Deadlines)
+// -----------------------------------------------------------------------------
+
+/// Source of the "now" that TTL and idle deadlines are compared to.
+/// Deadlines are stored as absolute milliseconds, so a clock should stay close
+/// to UNIX time if snapshots are shared between processes.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now_secs(&self) -> u64;
+
+    /// Millisecond reading used for deadlines. Second-only clocks get
+    /// `now_secs() * 1000`.
+    fn now_millis(&self) -> u64 {
+        self.now_secs().saturating_mul(1_000)
+    }
+}
+
+/// Wall-clock time (`SystemTime`). The default; follows NTP adjustments.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn now_millis(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+}
+
+/// Wall time sampled once at construction, advanced by `Instant`.
+/// Immune to clock jumps after start-up.
+#[derive(Clone, Copy, Debug)]
+pub struct MonotonicClock {
+    base_millis: u64,
+    origin: Instant,
+}
+
+impl MonotonicClock {
+    pub fn new() -> Self {
+        Self {
+            base_millis: SystemClock.now_millis(),
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now_secs(&self) -> u64 {
+        self.now_millis() / 1_000
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.base_millis + self.origin.elapsed().as_millis() as u64
+    }
+}
+
+/// Manually driven clock for tests. Share it via `Arc` and advance it
+/// while the cache holds the other handle.
+#[derive(Debug, Default)]
+pub struct MockClock {
+    // Milliseconds
+    now: AtomicU64,
+}
+
+impl MockClock {
+    pub fn new(start_secs: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start_secs.saturating_mul(1_000)),
+        }
+    }
+
+    pub fn set(&self, secs: u64) {
+        self.set_millis(secs.saturating_mul(1_000));
+    }
+
+    pub fn set_millis(&self, millis: u64) {
+        self.now.store(millis, Ordering::Release);
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.fetch_add(by.as_millis() as u64, Ordering::AcqRel);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_secs(&self) -> u64 {
+        self.now_millis() / 1_000
+    }
+
+    fn now_millis(&self) -> u64 {
+        self.now.load(Ordering::Acquire)
+    }
+}
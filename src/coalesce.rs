@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crossbeam::utils::CachePadded;
+use parking_lot::Mutex;
+
+use crate::stats::{STRIPES, current_stripe};
+
+// -----------------------------------------------------------------------------
+// Read Signal Coalescing
+// -----------------------------------------------------------------------------
+
+/// Per-thread-striped `(key, count)` maps filled by the read path.
+/// A hot key costs one map entry instead of one channel slot per hit.
+pub(crate) struct ReadCoalescer<K> {
+    stripes: [CachePadded<Mutex<HashMap<K, u64>>>; STRIPES],
+    flush_threshold: usize,
+}
+
+impl<K> ReadCoalescer<K>
+where
+    K: Hash + Eq + Clone,
+{
+    pub(crate) fn new(flush_threshold: usize) -> Self {
+        Self {
+            stripes: std::array::from_fn(|_| CachePadded::new(Mutex::new(HashMap::new()))),
+            flush_threshold,
+        }
+    }
+
+    /// Counts a hit. Once the caller's stripe holds `flush_threshold`
+    /// distinct keys it is drained and returned for sending.
+    pub(crate) fn record(&self, key: &K) -> Option<Vec<(K, u64)>> {
+        let mut stripe = self.stripes[current_stripe()].lock();
+        match stripe.get_mut(key) {
+            Some(count) => *count += 1,
+            None => {
+                stripe.insert(key.clone(), 1);
+            }
+        }
+
+        if stripe.len() >= self.flush_threshold {
+            return Some(stripe.drain().collect());
+        }
+        None
+    }
+
+    /// Drains every stripe.
+    pub(crate) fn drain(&self) -> Vec<(K, u64)> {
+        let mut counts = Vec::new();
+        for stripe in &self.stripes {
+            counts.extend(stripe.lock().drain());
+        }
+        counts
+    }
+}
@@ -0,0 +1,76 @@
+use std::hash::{BuildHasher, Hash};
+
+use crossbeam::channel::Sender;
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Write Commands (Actor Mode)
+// -----------------------------------------------------------------------------
+
+/// A write submitted over `DualCache::command_sender` and applied by the
+/// Daemon, so the submitting thread never takes the main lock.
+/// Unlike read signals, commands are never dropped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheCommand<K, V> {
+    Insert { key: K, value: V, ttl_secs: u64 },
+    /// Insert with the builder's default TTL.
+    Put { key: K, value: V },
+    Update { key: K, value: V },
+    Delete(K),
+    /// Publish everything applied so far, regardless of the `CommitPolicy`.
+    Commit,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Sender for the (unbounded, in-order) write-command channel drained by
+    /// the Daemon. Without a running Daemon, call `process_commands`.
+    pub fn command_sender(&self) -> Sender<CacheCommand<K, V>> {
+        self.command_tx.clone()
+    }
+
+    /// Applies every queued command under one lock acquisition, in order.
+    /// The writes count against the `CommitPolicy` once, at the end.
+    /// Returns the number of commands applied.
+    pub fn process_commands(&self) -> usize {
+        self.apply_commands(self.command_rx.try_iter())
+    }
+
+    pub(crate) fn apply_commands(&self, commands: impl IntoIterator<Item = CacheCommand<K, V>>) -> usize {
+        let mut commands = commands.into_iter().peekable();
+        if commands.peek().is_none() {
+            return 0;
+        }
+
+        let mut guard = self.main.lock();
+        let mut applied = 0;
+        let mut writes = 0;
+        for command in commands {
+            applied += 1;
+            match command {
+                CacheCommand::Insert { key, value, ttl_secs } => guard.gatsby_insert(key, value, ttl_secs),
+                CacheCommand::Put { key, value } => guard.gatsby_insert(key, value, self.default_ttl),
+                CacheCommand::Update { key, value } => guard.update_value(&key, value),
+                CacheCommand::Delete(key) => {
+                    guard.double_swap_delete(&key);
+                }
+                CacheCommand::Commit => {
+                    self.publish(&mut guard);
+                    writes = 0;
+                    continue;
+                }
+            }
+            writes += 1;
+        }
+
+        if writes > 0 {
+            self.after_writes(&mut guard, writes);
+        }
+        applied
+    }
+}
@@ -0,0 +1,327 @@
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crossbeam::channel::{Receiver, Select, Sender, bounded};
+
+use crate::time::Instant;
+use crate::{CacheCommand, DualCache, Signal};
+
+// -----------------------------------------------------------------------------
+// Built-in Daemon (The Maintenance Worker)
+// -----------------------------------------------------------------------------
+
+/// Scheduling for the built-in daemon spawned by `DualCache::spawn_daemon`.
+#[derive(Clone, Debug)]
+pub struct DaemonConfig {
+    /// How often the membrane (`maintenance`) is recalculated.
+    pub maintenance_interval: Duration,
+    /// How often `main` is published to the mirror (`commit`).
+    pub commit_interval: Duration,
+    /// Max signals drained per wake-up before checking the timers again.
+    pub max_batch: usize,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            maintenance_interval: Duration::from_secs(1),
+            commit_interval: Duration::from_millis(100),
+            max_batch: 1_024,
+        }
+    }
+}
+
+// One cache served by a daemon thread. Only a `Weak` reference is kept,
+// so dropping the last `Arc` disconnects `rx` and retires the lane; the
+// thread ends once all of its lanes are retired.
+pub(crate) struct DaemonLane<K, V, S>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Weak<DualCache<K, V, S>>,
+    rx: Receiver<Signal<K>>,
+    command_rx: Receiver<CacheCommand<K, V>>,
+}
+
+// Daemon run states, shared through `DaemonHandle::state`
+const RUNNING: u8 = 0;
+const STOP: u8 = 1;
+const CLOSE: u8 = 2;
+
+/// Handle to a running daemon thread.
+/// Dropping the handle detaches the thread; call `shutdown` or `close` to stop it.
+/// A signal daemon also exits on its own once the last `Arc<DualCache>` is
+/// dropped, and a checkpoint thread once its `Checkpointer` is dropped.
+pub struct DaemonHandle {
+    state: Arc<AtomicU8>,
+    // Dropped on stop to wake a daemon blocked in `select!`
+    wake: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl DaemonHandle {
+    /// Signals the daemon to stop and waits for the thread to exit.
+    /// Queued signals are left unprocessed.
+    pub fn shutdown(self) {
+        self.stop_with(STOP);
+    }
+
+    /// Graceful stop: the daemon drains queued signals and commands, flushes
+    /// coalesced reads, runs a final commit, then the thread is joined.
+    pub fn close(self) {
+        self.stop_with(CLOSE);
+    }
+
+    fn stop_with(mut self, state: u8) {
+        self.state.store(state, Ordering::Release);
+        self.wake.take();
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|t| t.is_finished())
+    }
+}
+
+// Helper: Stops selecting on a lane whose cache was dropped, so the other
+// lanes on the same worker keep being served. True once every lane is gone.
+fn retire(select: &mut Select<'_>, retired: &mut [bool], lane: usize) -> bool {
+    if !retired[lane] {
+        retired[lane] = true;
+        select.remove(2 * lane);
+        select.remove(2 * lane + 1);
+    }
+    retired.iter().all(|&gone| gone)
+}
+
+// Helper: Runs `task` every `interval` on a named thread until shut down,
+// or until `task` returns false (what it serves is gone).
+// `task` runs once more after `shutdown` wakes the thread.
+pub(crate) fn spawn_periodic(
+    name: &str,
+    interval: Duration,
+    mut task: impl FnMut() -> bool + Send + 'static,
+) -> DaemonHandle {
+    let state = Arc::new(AtomicU8::new(RUNNING));
+    let stop_flag = Arc::clone(&state);
+
+    let thread = thread::Builder::new()
+        .name(name.into())
+        .spawn(move || {
+            // A shutdown before the first park leaves an unpark token,
+            // so the final run still happens
+            loop {
+                thread::park_timeout(interval);
+                if !task() || stop_flag.load(Ordering::Acquire) != RUNNING {
+                    break;
+                }
+            }
+        })
+        .unwrap_or_else(|_| panic!("failed to spawn {name} thread"));
+
+    DaemonHandle {
+        state,
+        wake: None,
+        thread: Some(thread),
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Spawns a dedicated thread that owns `rx`, applies read signals and
+    /// write commands (see `command_sender`), and runs `maintenance` / `commit` on the configured intervals.
+    /// The thread only holds a `Weak` reference: dropping the last `Arc`
+    /// disconnects `rx` and the daemon exits.
+    pub fn spawn_daemon(self: &Arc<Self>, rx: Receiver<Signal<K>>, config: DaemonConfig) -> DaemonHandle {
+        Self::spawn_worker("dualcache-daemon", vec![self.daemon_lane(rx)], config)
+    }
+
+    // Helper: What a daemon thread keeps for one cache
+    pub(crate) fn daemon_lane(self: &Arc<Self>, rx: Receiver<Signal<K>>) -> DaemonLane<K, V, S> {
+        DaemonLane {
+            cache: Arc::downgrade(self),
+            rx,
+            command_rx: self.command_rx.clone(),
+        }
+    }
+
+    // Helper: Spawns one thread serving every lane in `lanes`
+    pub(crate) fn spawn_worker(
+        name: &str,
+        lanes: Vec<DaemonLane<K, V, S>>,
+        config: DaemonConfig,
+    ) -> DaemonHandle {
+        let state = Arc::new(AtomicU8::new(RUNNING));
+        let stop_flag = Arc::clone(&state);
+        let (wake, woken) = bounded(0);
+
+        let thread = thread::Builder::new()
+            .name(name.into())
+            .spawn(move || Self::run_daemon(&lanes, woken, config, &stop_flag))
+            .unwrap_or_else(|_| panic!("failed to spawn {name} thread"));
+
+        DaemonHandle {
+            state,
+            wake: Some(wake),
+            thread: Some(thread),
+        }
+    }
+
+    fn run_daemon(
+        lanes: &[DaemonLane<K, V, S>],
+        woken: Receiver<()>,
+        config: DaemonConfig,
+        state: &AtomicU8,
+    ) {
+        let now = Instant::now();
+        let mut next_maintenance = now + config.maintenance_interval;
+        let mut next_commit = now + config.commit_interval;
+
+        // Operation `2 * i` is lane i's signals, `2 * i + 1` its commands
+        let mut select = Select::new();
+        for lane in lanes {
+            select.recv(&lane.rx);
+            select.recv(&lane.command_rx);
+        }
+        let wake_op = select.recv(&woken);
+        let mut retired = vec![false; lanes.len()];
+
+        while state.load(Ordering::Acquire) == RUNNING {
+            // Sleep on every channel until the next timer is due
+            let deadline = next_maintenance.min(next_commit);
+            let timeout = deadline.saturating_duration_since(Instant::now());
+
+            if let Ok(op) = select.select_timeout(timeout) {
+                let index = op.index();
+                if index == wake_op {
+                    // Nothing is ever sent: this is a stop, or a detached handle
+                    // (stays disconnected, so it is dropped from the select)
+                    let _ = op.recv(&woken);
+                    select.remove(wake_op);
+                    continue;
+                }
+                let lane = &lanes[index / 2];
+                if index % 2 == 0 {
+                    // Every sender is gone: the cache itself was dropped
+                    let (Ok(signal), Some(cache)) = (op.recv(&lane.rx), lane.cache.upgrade()) else {
+                        if retire(&mut select, &mut retired, index / 2) {
+                            return;
+                        }
+                        continue;
+                    };
+                    #[cfg(feature = "tracing")]
+                    let _batch = tracing::trace_span!("daemon_batch").entered();
+                    // Drain whatever else is queued, bounded so timers are not starved;
+                    // the batch's read signals share one lock
+                    let queued = lane.rx.try_iter().take(config.max_batch);
+                    cache.process_signals(std::iter::once(signal).chain(queued));
+                } else {
+                    let (Ok(command), Some(cache)) = (op.recv(&lane.command_rx), lane.cache.upgrade()) else {
+                        if retire(&mut select, &mut retired, index / 2) {
+                            return;
+                        }
+                        continue;
+                    };
+                    let queued = lane.command_rx.try_iter().take(config.max_batch);
+                    cache.apply_commands(std::iter::once(command).chain(queued));
+                }
+            }
+
+            let now = Instant::now();
+            let maintenance_due = now >= next_maintenance;
+            let commit_due = now >= next_commit;
+            for (i, lane) in lanes.iter().enumerate() {
+                if retired[i] {
+                    continue;
+                }
+                let Some(cache) = lane.cache.upgrade() else {
+                    if retire(&mut select, &mut retired, i) {
+                        return;
+                    }
+                    continue;
+                };
+                if maintenance_due {
+                    cache.maintenance();
+                }
+                if commit_due {
+                    #[cfg(feature = "tracing")]
+                    let _commit = tracing::debug_span!("daemon_commit").entered();
+                    cache.flush_read_signals();
+                    cache.commit();
+                    #[cfg(feature = "metrics")]
+                    cache.export_metrics();
+                }
+            }
+            if maintenance_due {
+                next_maintenance = now + config.maintenance_interval;
+            }
+            if commit_due {
+                next_commit = now + config.commit_interval;
+            }
+        }
+
+        if state.load(Ordering::Acquire) == CLOSE {
+            for lane in lanes {
+                if let Some(cache) = lane.cache.upgrade() {
+                    cache.drain_and_commit(&lane.rx);
+                }
+            }
+        }
+    }
+
+    // Helper: Final pass for a graceful close: everything queued is applied and published
+    pub(crate) fn drain_and_commit(&self, rx: &Receiver<Signal<K>>) {
+        self.process_signals(rx.try_iter());
+        self.apply_commands(self.command_rx.try_iter());
+        self.flush_read_signals();
+        self.commit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DualCacheBuilder;
+
+    // Helper: Polls `done` for up to a second
+    fn eventually(mut done: impl FnMut() -> bool) -> bool {
+        let deadline = std::time::Instant::now() + Duration::from_secs(1);
+        while std::time::Instant::now() < deadline {
+            if done() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn dropped_cache_retires_only_its_lane() {
+        let (a, rx_a) = DualCacheBuilder::new(16).build::<u32, u32>();
+        let (b, rx_b) = DualCacheBuilder::new(16).build::<u32, u32>();
+        let config = DaemonConfig {
+            commit_interval: Duration::from_millis(5),
+            ..DaemonConfig::default()
+        };
+        let lanes = vec![a.daemon_lane(rx_a), b.daemon_lane(rx_b)];
+        let handle = DualCache::spawn_worker("dualcache-test", lanes, config);
+
+        drop(a);
+        b.insert(1, 1, 60);
+        assert!(eventually(|| b.get(&1) == Some(1)), "surviving lane is no longer committed");
+        assert!(!handle.is_finished());
+
+        drop(b);
+        assert!(eventually(|| handle.is_finished()), "worker outlived its last lane");
+    }
+}
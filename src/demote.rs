@@ -0,0 +1,71 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Demotion (Draining the Cold Tail)
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Removes up to `n` live entries from the bottom of the ranking, coldest
+    /// first, and hands them to the caller (e.g. to demote them to a remote
+    /// tier). Only the region past the evict point is drained, so the safe
+    /// zone is never touched; pinned entries are skipped and expired ones are
+    /// expired rather than returned. Counts as eviction (listener, events).
+    pub fn drain_coldest(&self, n: usize) -> Vec<(K, V)> {
+        self.write(|cache| cache.drain_coldest(n))
+    }
+
+    /// The single coldest entry past the evict point, removed.
+    pub fn pop_coldest(&self) -> Option<(K, V)> {
+        self.drain_coldest(1).pop()
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Pops nodes off the tail (garbage dropped along the way) until
+    // `n` live ones are collected or only the safe zone and pinned nodes remain
+    fn drain_coldest(&mut self, n: usize) -> Vec<(K, V)> {
+        let now = self.current_time();
+        let mut drained = Vec::with_capacity(n.min(self.arena.len()));
+        while drained.len() < n {
+            // Coldest slot past the membrane that is not a live pinned node
+            let Some(victim) = (self.evict_point..self.arena.len())
+                .rev()
+                .find(|&i| !self.arena[i].pinned || !self.is_live(i))
+            else {
+                break;
+            };
+            if self.is_live(victim) && self.arena[victim].is_expired(now) {
+                // Leaves garbage behind, dropped by a later pass
+                self.invalidate_expired(victim);
+                continue;
+            }
+
+            let tail = self.arena.len() - 1;
+            self.swap_nodes(victim, tail);
+            let Some(node) = self.arena.pop() else { break };
+            self.dirty = true;
+            self.counter_sum = self.counter_sum.saturating_sub(self.node_frequency(&node));
+            if self.index.get(&node.key) == Some(&tail) {
+                self.index.remove(&node.key);
+                self.evictions += 1;
+                self.release_evicted(&node);
+                drained.push((node.key, node.value));
+            } else {
+                self.garbage = self.garbage.saturating_sub(1);
+            }
+        }
+        drained
+    }
+}
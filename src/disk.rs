@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{CacheListener, DualCache, Node, SnapshotError, Ttl};
+
+// -----------------------------------------------------------------------------
+// Disk-Backed L2 Tier (feature = "disk")
+// -----------------------------------------------------------------------------
+
+// One line of the log. `value: None` is a tombstone.
+#[derive(Serialize, Deserialize)]
+struct Record<K, V> {
+    key: K,
+    value: Option<V>,
+    deadline: u64,
+}
+
+struct LogState<K> {
+    file: File,
+    // Offset and length of each key's latest record
+    index: HashMap<K, (u64, usize)>,
+    end: u64,
+    // Superseded records, reclaimable by `compact`
+    stale: usize,
+}
+
+/// Append-only, log-structured key/value file (one JSON record per line)
+/// with an in-memory offset index rebuilt on open.
+pub struct DiskTier<K, V> {
+    path: PathBuf,
+    state: Mutex<LogState<K>>,
+    _values: std::marker::PhantomData<fn() -> V>,
+}
+
+impl<K, V> DiskTier<K, V>
+where
+    K: Hash + Eq + Clone + Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Opens (or creates) the log at `path`, replaying it to rebuild the index.
+    /// A torn final line (crash mid-append) is cut off, so later appends start
+    /// on a fresh line.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+
+        let mut index = HashMap::new();
+        let mut stale = 0;
+        let mut offset = 0u64;
+        let mut reader = BufReader::new(&file);
+        let mut line = String::new();
+        while reader.read_line(&mut line)? > 0 {
+            if !line.ends_with('\n') {
+                file.set_len(offset)?;
+                break;
+            }
+            let record: Record<K, V> = serde_json::from_str(line.trim_end())?;
+            let len = line.len();
+            let previous = match record.value {
+                Some(_) => index.insert(record.key, (offset, len)),
+                None => index.remove(&record.key),
+            };
+            stale += usize::from(previous.is_some()) + usize::from(record.value.is_none());
+            offset += len as u64;
+            line.clear();
+        }
+
+        Ok(Self {
+            path,
+            state: Mutex::new(LogState {
+                file,
+                index,
+                end: offset,
+                stale,
+            }),
+            _values: std::marker::PhantomData,
+        })
+    }
+
+    /// Value and absolute deadline (milliseconds) stored for `key`.
+    pub fn get(&self, key: &K) -> Result<Option<(V, u64)>, SnapshotError> {
+        let mut state = self.state.lock();
+        let Some(&(offset, len)) = state.index.get(key) else {
+            return Ok(None);
+        };
+
+        let mut buf = vec![0; len];
+        state.file.seek(SeekFrom::Start(offset))?;
+        state.file.read_exact(&mut buf)?;
+        let record: Record<K, V> = serde_json::from_slice(&buf)?;
+        Ok(record.value.map(|value| (value, record.deadline)))
+    }
+
+    pub fn put(&self, key: &K, value: &V, deadline: u64) -> Result<(), SnapshotError> {
+        let mut state = self.state.lock();
+        let (offset, len) = Self::append(&mut state, key, Some(value), deadline)?;
+        if state.index.insert(key.clone(), (offset, len)).is_some() {
+            state.stale += 1;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&self, key: &K) -> Result<(), SnapshotError> {
+        let mut state = self.state.lock();
+        if state.index.remove(key).is_some() {
+            Self::append(&mut state, key, None, 0)?;
+            state.stale += 2;
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Rewrites the log with only the live records. Worth running once the
+    /// number of superseded records (`stale_records`) dominates.
+    pub fn compact(&self) -> Result<(), SnapshotError> {
+        let mut state = self.state.lock();
+        let tmp_path = self.path.with_extension("compact");
+        let mut tmp = File::create(&tmp_path)?;
+
+        let mut index = HashMap::with_capacity(state.index.len());
+        let mut end = 0u64;
+        let live: Vec<(K, (u64, usize))> = state.index.iter().map(|(k, &loc)| (k.clone(), loc)).collect();
+        for (key, (offset, len)) in live {
+            let mut buf = vec![0; len];
+            state.file.seek(SeekFrom::Start(offset))?;
+            state.file.read_exact(&mut buf)?;
+            tmp.write_all(&buf)?;
+            index.insert(key, (end, len));
+            end += len as u64;
+        }
+        tmp.sync_all()?;
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        state.file = OpenOptions::new().read(true).append(true).open(&self.path)?;
+        state.index = index;
+        state.end = end;
+        state.stale = 0;
+        Ok(())
+    }
+
+    /// Records superseded by later writes or tombstones.
+    pub fn stale_records(&self) -> usize {
+        self.state.lock().stale
+    }
+
+    // Helper: Appends one record line, returning its location
+    fn append(
+        state: &mut LogState<K>,
+        key: &K,
+        value: Option<&V>,
+        deadline: u64,
+    ) -> Result<(u64, usize), SnapshotError> {
+        #[derive(Serialize)]
+        struct RecordRef<'a, K, V> {
+            key: &'a K,
+            value: Option<&'a V>,
+            deadline: u64,
+        }
+
+        let mut line = serde_json::to_vec(&RecordRef { key, value, deadline })?;
+        line.push(b'\n');
+        state.file.write_all(&line)?;
+
+        let offset = state.end;
+        state.end += line.len() as u64;
+        Ok((offset, line.len()))
+    }
+}
+
+// Demotes cliff-edge victims into the disk tier
+struct Demote<K, V> {
+    disk: Arc<DiskTier<K, V>>,
+}
+
+impl<K, V> CacheListener<K, V> for Demote<K, V>
+where
+    K: Hash + Eq + Clone + Serialize + DeserializeOwned + Send + Sync,
+    V: Serialize + DeserializeOwned + Send + Sync,
+{
+    fn on_evict(&self, node: &Node<K, V>) {
+        // Best effort: a failed write only loses the L2 copy
+        let _ = self.disk.put(&node.key, &node.value, node.time_stamp);
+    }
+}
+
+/// A `DualCache` (L1) over a `DiskTier` (L2): entries discarded by the cliff
+/// edge are written to disk instead of dropped, and L1 misses are served from
+/// disk (promoting the entry back into L1 with its remaining TTL).
+///
+/// Installs its own `CacheListener` on the L1 cache; demotion happens under
+/// the main lock, so eviction pays for one disk append per victim.
+pub struct TieredDualCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V>>,
+    disk: Arc<DiskTier<K, V>>,
+}
+
+impl<K, V> TieredDualCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    pub fn new(cache: Arc<DualCache<K, V>>, disk: Arc<DiskTier<K, V>>) -> Self {
+        cache.set_listener(Arc::new(Demote {
+            disk: Arc::clone(&disk),
+        }));
+        Self { cache, disk }
+    }
+
+    pub fn cache(&self) -> &Arc<DualCache<K, V>> {
+        &self.cache
+    }
+
+    pub fn disk(&self) -> &Arc<DiskTier<K, V>> {
+        &self.disk
+    }
+
+    /// L1, then L2. An L2 hit moves the entry back into L1 (visible to plain
+    /// `get` after the next commit). I/O errors are treated as L2 misses.
+    pub fn get(&self, key: &K) -> Option<V> {
+        if let Some(value) = self.cache.get(key) {
+            return Some(value);
+        }
+
+        let (value, deadline) = self.disk.get(key).ok()??;
+        let _ = self.disk.remove(key);
+
+        let now = self.cache.mirror.load().current_time();
+        if deadline < now {
+            return None;
+        }
+        self.cache.insert_with_ttl(key.clone(), value.clone(), Ttl::Millis(deadline - now));
+        Some(value)
+    }
+
+    pub fn insert(&self, key: K, value: V, ttl_secs: u64) {
+        // Drop any older L2 copy so it cannot resurface after eviction
+        let _ = self.disk.remove(&key);
+        self.cache.insert(key, value, ttl_secs);
+    }
+
+    pub fn delete(&self, key: &K) {
+        let _ = self.disk.remove(key);
+        self.cache.delete(key);
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use std::thread;
+
+use crossbeam::channel::{Sender, unbounded};
+
+use crate::segmented::Segments;
+
+// -----------------------------------------------------------------------------
+// Deferred Drop (Background Dropper Thread)
+// -----------------------------------------------------------------------------
+
+// Hands evicted nodes to a thread that drops them, so freeing large values
+// does not happen under the main lock. Shared by `main` and its mirrors;
+// the thread exits once every clone is gone and the channel drains.
+pub(crate) struct Dropper<T> {
+    tx: Sender<T>,
+}
+
+// Not derived: that would require `T: Clone`
+impl<T> Clone for Dropper<T> {
+    fn clone(&self) -> Self {
+        Self { tx: self.tx.clone() }
+    }
+}
+
+// What the dropper frees: segments cut off the arena, or a mirror snapshot
+// replaced by a commit. Segments cut while the mirror still shared them are
+// only freed with that snapshot, so it has to go to the dropper as well.
+// Never read: the payload is only held to be dropped.
+#[allow(dead_code)]
+pub(crate) enum Garbage<T, C> {
+    Segments(Segments<T>),
+    Snapshot(Arc<C>),
+}
+
+impl<T: Send + 'static> Dropper<T> {
+    pub(crate) fn spawn() -> Self {
+        let (tx, rx) = unbounded::<T>();
+        thread::Builder::new()
+            .name("dualcache-dropper".into())
+            .spawn(move || rx.iter().for_each(drop))
+            .expect("failed to spawn dualcache-dropper thread");
+        Self { tx }
+    }
+}
+
+impl<T> Dropper<T> {
+    // Helper: Queues `garbage` for the dropper (dropped inline if it is gone)
+    pub(crate) fn defer(&self, garbage: T) {
+        let _ = self.tx.send(garbage);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use parking_lot::MutexGuard;
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Entry API (Read-Modify-Write under one lock acquisition)
+// -----------------------------------------------------------------------------
+
+/// A view into a single key of the main cache, obtained via `DualCache::entry`.
+/// The main lock is held until the entry is consumed or dropped, so keep it short.
+/// Changes follow the usual visibility rules: readers see them after a commit.
+pub enum Entry<'a, K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+pub struct OccupiedEntry<'a, K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    cache: &'a DualCache<K, V, S>,
+    guard: MutexGuard<'a, Cache<K, V, S>>,
+    key: K,
+    idx: usize,
+    // Set by `get_mut`; the update is logged, published and announced once
+    // the caller is done with the value (see `settle`)
+    modified: bool,
+}
+
+pub struct VacantEntry<'a, K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    cache: &'a DualCache<K, V, S>,
+    guard: MutexGuard<'a, Cache<K, V, S>>,
+    key: K,
+}
+
+/// Returned by `DualCache::try_insert` when the key is already live.
+#[derive(Debug)]
+pub struct OccupiedError<V> {
+    /// The value currently stored under the key.
+    pub existing: V,
+    /// The value that was not inserted.
+    pub value: V,
+}
+
+impl<V> fmt::Display for OccupiedError<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key is already present in the cache")
+    }
+}
+
+impl<V: fmt::Debug> std::error::Error for OccupiedError<V> {}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Locks `main` and returns the entry for `key`.
+    pub fn entry(&self, key: K) -> Entry<'_, K, V, S> {
+        let guard = self.main.lock();
+        match guard.position(&key) {
+            Some(idx) => Entry::Occupied(OccupiedEntry {
+                cache: self,
+                guard,
+                key,
+                idx,
+                modified: false,
+            }),
+            None => Entry::Vacant(VacantEntry {
+                cache: self,
+                guard,
+                key,
+            }),
+        }
+    }
+
+    /// Insert-if-absent: unlike `insert`, an existing live key is left
+    /// untouched and its value returned in the error. An entry whose TTL
+    /// has lapsed (but was not yet invalidated) counts as absent.
+    pub fn try_insert(&self, key: K, value: V, ttl_secs: u64) -> Result<(), OccupiedError<V>> {
+        let mut guard = self.main.lock();
+        if let Some(idx) = guard.position(&key) {
+            if !guard.arena[idx].is_expired(guard.current_time()) {
+                let existing = guard.arena[idx].value.clone();
+                return Err(OccupiedError { existing, value });
+            }
+            guard.invalidate_expired(idx);
+        }
+        guard.gatsby_insert(key, value, ttl_secs);
+        self.after_write(&mut guard);
+        Ok(())
+    }
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Returns the current value, inserting `value` with `ttl_secs` if vacant.
+    pub fn or_insert(self, value: V, ttl_secs: u64) -> V {
+        self.or_insert_with(ttl_secs, || value)
+    }
+
+    /// Returns the current value, inserting the result of `init` if vacant.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, ttl_secs: u64, init: F) -> V {
+        match self {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => entry.insert(init(), ttl_secs),
+        }
+    }
+
+    /// Modifies an occupied value in place; counter and rank are untouched.
+    /// Counts as an update, like `OccupiedEntry::insert`.
+    pub fn and_modify<F: FnOnce(&mut V)>(self, modify: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                modify(entry.get_mut());
+                entry.settle();
+                Entry::Occupied(entry)
+            }
+            vacant => vacant,
+        }
+    }
+
+    /// Removes the entry if present, returning its value.
+    pub fn remove(self) -> Option<V> {
+        match self {
+            Entry::Occupied(entry) => Some(entry.remove()),
+            Entry::Vacant(_) => None,
+        }
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        &self.guard.arena[self.idx].value
+    }
+
+    /// Mutable access counts as an update: the node's version is bumped, and
+    /// the change is logged, published and reported (`CacheEvent::Updated`)
+    /// when the entry is dropped or consumed.
+    pub fn get_mut(&mut self) -> &mut V {
+        if !self.modified {
+            self.modified = true;
+            self.guard.begin_update(self.idx);
+        }
+        &mut self.guard.arena[self.idx].value
+    }
+
+    // Helper: Completes the update opened by `get_mut`, if any
+    fn settle(&mut self) {
+        if std::mem::take(&mut self.modified) {
+            self.guard.finish_update(self.idx);
+            self.cache.after_write(&mut self.guard);
+        }
+    }
+
+    /// Hit counter of the node (its ranking weight).
+    pub fn counter(&self) -> u64 {
+        self.guard.frequency(self.idx)
+    }
+
+    /// Replaces the value, returning the old one. Counter and rank are kept.
+    pub fn insert(&mut self, value: V) -> V {
+        let old = std::mem::replace(self.get_mut(), value);
+        self.settle();
+        old
+    }
+
+    /// Double-swap deletes the entry, returning its value.
+    pub fn remove(mut self) -> V {
+        self.settle();
+        let node = self
+            .guard
+            .double_swap_delete(&self.key)
+            .expect("occupied entry is live under the held lock");
+        self.cache.after_write(&mut self.guard);
+        node.value
+    }
+}
+
+impl<'a, K, V, S> Drop for OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        self.settle();
+    }
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Gatsby-inserts the value, returning a clone of it.
+    pub fn insert(mut self, value: V, ttl_secs: u64) -> V {
+        self.guard.gatsby_insert(self.key, value.clone(), ttl_secs);
+        self.cache.after_write(&mut self.guard);
+        value
+    }
+}
@@ -0,0 +1,94 @@
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use crossbeam::channel::{Receiver, Sender, unbounded};
+use parking_lot::Mutex;
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Change Events (Subscriptions)
+// -----------------------------------------------------------------------------
+
+/// A change to the authoritative (main) state, delivered to `subscribe`rs in
+/// the order it happened. Entry events precede the `Committed` that makes them
+/// visible to readers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheEvent<K> {
+    /// A new key was placed.
+    Inserted(K),
+    /// An existing key had its value replaced.
+    Updated(K),
+    /// Cliff-edge (or weight) eviction discarded the entry.
+    Evicted(K),
+    /// The entry was invalidated because its TTL or idle window passed.
+    Expired(K),
+    /// The entry was removed by an explicit delete.
+    Deleted(K),
+    /// A commit published the given generation to the mirror.
+    Committed(u64),
+}
+
+// Fan-out to every live subscriber; shared (not copied) with the mirror
+pub(crate) struct EventBus<K> {
+    subscribers: Mutex<Vec<Sender<CacheEvent<K>>>>,
+}
+
+impl<K: Clone> EventBus<K> {
+    fn new() -> Self {
+        Self {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    // Helper: Delivers `event`, forgetting subscribers whose receiver is gone
+    fn emit(&self, event: CacheEvent<K>) {
+        self.subscribers
+            .lock()
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// A new stream of `CacheEvent`s. Events are emitted under the main lock
+    /// into an unbounded channel: a subscriber that stops draining grows its
+    /// queue, so drop the receiver once it is no longer needed.
+    pub fn subscribe(&self) -> Receiver<CacheEvent<K>> {
+        let (tx, rx) = unbounded();
+        let mut guard = self.main.lock();
+        guard
+            .events
+            .get_or_insert_with(|| Arc::new(EventBus::new()))
+            .subscribers
+            .lock()
+            .push(tx);
+        rx
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Emits an event if anyone subscribed or watches keys (the key
+    // is only cloned then)
+    pub(crate) fn emit(&self, event: impl FnOnce() -> CacheEvent<K>) {
+        if self.events.is_none() && self.watches.is_none() {
+            return;
+        }
+        let event = event();
+        if let Some(watches) = &self.watches {
+            watches.record(&event);
+        }
+        if let Some(events) = &self.events {
+            events.emit(event);
+        }
+    }
+}
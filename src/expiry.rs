@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Proactive Expiry (Deadline Wheel)
+// -----------------------------------------------------------------------------
+
+// Width of one wheel bucket in milliseconds
+const BUCKET_MILLIS: u64 = 1_000;
+
+// Keys bucketed by deadline second, so `maintenance` finds expired entries
+// without scanning the arena. Entries are hints: a due key is re-checked
+// against its node, and one whose deadline moved later is rescheduled.
+// Shared (not copied) with the mirror like the event bus.
+pub(crate) struct ExpiryWheel<K> {
+    buckets: BTreeMap<u64, Vec<K>>,
+}
+
+impl<K> ExpiryWheel<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            buckets: BTreeMap::new(),
+        }
+    }
+
+    // Helper: Files `key` under `deadline` (never-expiring deadlines are skipped)
+    pub(crate) fn schedule(&mut self, key: K, deadline: u64) {
+        if deadline == u64::MAX {
+            return;
+        }
+        self.buckets.entry(deadline / BUCKET_MILLIS).or_default().push(key);
+    }
+
+    // Helper: Takes every key filed in a bucket that started by `now`
+    fn take_due(&mut self, now: u64) -> Vec<K> {
+        let pending = self.buckets.split_off(&(now / BUCKET_MILLIS + 1));
+        let due = std::mem::replace(&mut self.buckets, pending);
+        due.into_values().flatten().collect()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.buckets.clear();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.buckets.values().map(Vec::len).sum()
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Invalidates every entry in `main` whose TTL (or idle window) has passed,
+    /// read or not, freeing its slot. Uses the expiry wheel when enabled
+    /// (`DualCacheBuilder::expiry_wheel`, then also run by `maintenance`),
+    /// otherwise scans the arena. Returns the number expired.
+    pub fn purge_expired(&self) -> usize {
+        self.write(|cache| {
+            if cache.expiry.is_some() {
+                return cache.expire_due();
+            }
+            let now = cache.current_time();
+            let expired: Vec<K> = (0..cache.arena.len())
+                .filter(|&i| cache.is_live(i) && cache.arena[i].is_expired(now))
+                .map(|i| cache.arena[i].key.clone())
+                .collect();
+            cache.invalidate_keys(&expired)
+        })
+    }
+
+    /// Keys waiting on the expiry wheel (including hints for entries since
+    /// deleted or refreshed); 0 when the wheel is off.
+    pub fn scheduled_expirations(&self) -> usize {
+        self.main.lock().expiry.as_ref().map_or(0, |wheel| wheel.lock().len())
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Expires the due keys of the wheel; returns how many expired
+    pub(crate) fn expire_due(&mut self) -> usize {
+        let Some(wheel) = self.expiry.clone() else { return 0 };
+        let now = self.current_time();
+        let due = wheel.lock().take_due(now);
+
+        let mut expired = 0;
+        for key in due {
+            // Deleted or evicted since it was scheduled
+            let Some(idx) = self.position(&key) else { continue };
+            let node = &self.arena[idx];
+            if node.is_expired(now) {
+                self.invalidate_expired(idx);
+                expired += 1;
+            } else if !node.pinned {
+                // Its deadline moved later
+                let deadline = node.time_stamp;
+                wheel.lock().schedule(key, deadline);
+            }
+        }
+        expired
+    }
+
+    // Helper: Invalidates each still-present key as expired
+    fn invalidate_keys(&mut self, keys: &[K]) -> usize {
+        let mut expired = 0;
+        for key in keys {
+            // Invalidation swaps nodes around, so resolve each key again
+            if let Some(idx) = self.position(key) {
+                self.invalidate_expired(idx);
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    // Helper: Files a freshly placed node on the wheel
+    pub(crate) fn schedule_expiry(&self, idx: usize) {
+        let node = &self.arena[idx];
+        if let Some(wheel) = &self.expiry
+            && !node.pinned
+        {
+            wheel.lock().schedule(node.key.clone(), node.time_stamp);
+        }
+    }
+
+    // Helper: Rewrites a node's hard deadline (logged, so `recover` restores
+    // it). Only an earlier deadline needs a new wheel entry; a later one is
+    // rescheduled when the old entry is due.
+    pub(crate) fn move_deadline(&mut self, idx: usize, deadline: u64) {
+        let node = &mut self.arena[idx];
+        let earlier = deadline < node.time_stamp;
+        node.time_stamp = deadline;
+        if earlier {
+            self.schedule_expiry(idx);
+        }
+        #[cfg(feature = "disk")]
+        self.log_deadline(idx);
+    }
+}
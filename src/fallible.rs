@@ -0,0 +1,86 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Fallible Loads (Error Caching)
+// -----------------------------------------------------------------------------
+
+// Cached init errors by key: (deadline in clock milliseconds, the error)
+pub(crate) type ErrorSlots<K> = Mutex<HashMap<K, (u64, Arc<dyn Any + Send + Sync>)>>;
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Fallible `get_or_insert_with`: an `Ok` from `init` is inserted with
+    /// `ttl_secs`, an `Err` is returned and nothing is inserted. With
+    /// `DualCacheBuilder::error_ttl` set, the error is also remembered for
+    /// that long: calls for the key within the window get it back without
+    /// running `init`, so a failing upstream is not hit by every request.
+    pub fn get_or_try_insert_with<E, F>(&self, key: K, ttl_secs: u64, init: F) -> Result<V, E>
+    where
+        E: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Result<V, E>,
+    {
+        if let Some(value) = self.get(&key) {
+            return Ok(value);
+        }
+        if let Some(error) = self.cached_error::<E>(&key) {
+            return Err(error);
+        }
+
+        let result: Result<V, E> = self.write(|cache| {
+            // Re-check under the lock: another writer may have inserted meanwhile
+            if let Some(idx) = cache.unexpired_position(&key) {
+                return Ok(cache.arena[idx].value.clone());
+            }
+            let value = init()?;
+            cache.gatsby_insert(key.clone(), value.clone(), ttl_secs);
+            Ok(value)
+        });
+        if let Err(error) = &result
+            && self.error_ttl > 0
+        {
+            let until = self.mirror.load().current_time().saturating_add(self.error_ttl);
+            self.errors.lock().insert(key, (until, Arc::new(error.clone())));
+        }
+        result
+    }
+
+    /// Forgets a cached error so the next `get_or_try_insert_with` retries.
+    pub fn forget_error(&self, key: &K) -> bool {
+        self.errors.lock().remove(key).is_some()
+    }
+
+    // Helper: The unlapsed error cached for `key`, if it is an `E`
+    fn cached_error<E: Clone + 'static>(&self, key: &K) -> Option<E> {
+        if self.error_ttl == 0 {
+            return None;
+        }
+        let now = self.mirror.load().current_time();
+        let mut errors = self.errors.lock();
+        let (until, error) = errors.get(key)?;
+        if now > *until {
+            errors.remove(key);
+            return None;
+        }
+        error.downcast_ref::<E>().cloned()
+    }
+
+    // Helper: Drops lapsed cached errors (run by `maintenance`)
+    pub(crate) fn purge_errors(&self) {
+        if self.error_ttl == 0 {
+            return;
+        }
+        let now = self.mirror.load().current_time();
+        self.errors.lock().retain(|_, (until, _)| now <= *until);
+    }
+}
@@ -0,0 +1,230 @@
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use crossbeam::channel::Receiver;
+
+use crate::{DaemonConfig, DaemonHandle, DualCache, DualCacheBuilder, Signal};
+
+// -----------------------------------------------------------------------------
+// C FFI (feature = "ffi")
+// -----------------------------------------------------------------------------
+
+// C ABI over a `DualCache<Vec<u8>, Vec<u8>>`. Signatures are cbindgen-friendly:
+// the handle is opaque, buffers are (pointer, length) pairs, and values handed
+// out are owned `DualCacheBytes` released with `dualcache_bytes_free`. Build a
+// linkable library with `cargo rustc --release --features ffi --crate-type cdylib`.
+// A panic inside a call aborts the process rather than unwinding into C.
+
+type ByteCache = DualCache<Vec<u8>, Vec<u8>>;
+
+/// Opaque cache handle owned by the C side between `dualcache_new` and `dualcache_free`.
+pub struct DualCacheHandle {
+    cache: Arc<ByteCache>,
+    // Held until `dualcache_start_daemon` hands it to the daemon thread
+    rx: Option<Receiver<Signal<Vec<u8>>>>,
+    daemon: Option<DaemonHandle>,
+}
+
+/// A value copied out of the cache. Free it with `dualcache_bytes_free`.
+/// `ptr` is null (and `len` 0) when nothing was returned.
+#[repr(C)]
+pub struct DualCacheBytes {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+impl DualCacheBytes {
+    fn empty() -> Self {
+        Self {
+            ptr: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    fn from_vec(value: Vec<u8>) -> Self {
+        let boxed = value.into_boxed_slice();
+        let len = boxed.len();
+        Self {
+            ptr: Box::into_raw(boxed).cast(),
+            len,
+        }
+    }
+}
+
+// Helper: Borrows a C buffer; a null pointer reads as the empty slice
+unsafe fn bytes<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees `data` points to `len` readable bytes
+        unsafe { slice::from_raw_parts(data, len) }
+    }
+}
+
+// Helper: Resolves a handle pointer (null stays `None`)
+unsafe fn handle<'a>(cache: *const DualCacheHandle) -> Option<&'a DualCacheHandle> {
+    // SAFETY: the caller guarantees a non-null `cache` came from `dualcache_new`
+    unsafe { cache.as_ref() }
+}
+
+/// Creates a cache holding at most `capacity` entries.
+/// Reads are lossy-signaled: call `dualcache_start_daemon` (or drive
+/// `dualcache_maintenance` / `dualcache_commit` yourself).
+#[unsafe(no_mangle)]
+pub extern "C" fn dualcache_new(capacity: usize) -> *mut DualCacheHandle {
+    let (cache, rx) = DualCacheBuilder::new(capacity).build();
+    Box::into_raw(Box::new(DualCacheHandle {
+        cache,
+        rx: Some(rx),
+        daemon: None,
+    }))
+}
+
+/// Stops the daemon (after a final commit) and frees the cache.
+///
+/// # Safety
+/// `cache` must come from `dualcache_new` and not be used afterwards. Null is ignored.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_free(cache: *mut DualCacheHandle) {
+    if cache.is_null() {
+        return;
+    }
+    // SAFETY: ownership returns from C, per the contract above
+    let handle = unsafe { Box::from_raw(cache) };
+    if let Some(daemon) = handle.daemon {
+        daemon.close();
+    }
+}
+
+/// Spawns the built-in daemon with default intervals. Returns false if it
+/// already runs or `cache` is null.
+///
+/// # Safety
+/// `cache` must be null or a live handle from `dualcache_new`, not used
+/// concurrently with another `dualcache_start_daemon` or `dualcache_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_start_daemon(cache: *mut DualCacheHandle) -> bool {
+    // SAFETY: exclusive access per the contract above
+    let Some(handle) = (unsafe { cache.as_mut() }) else { return false };
+    let Some(rx) = handle.rx.take() else { return false };
+    handle.daemon = Some(handle.cache.spawn_daemon(rx, DaemonConfig::default()));
+    true
+}
+
+/// Looks `key` up in the mirror. On a hit returns a copy of the value
+/// (free it with `dualcache_bytes_free`); on a miss `ptr` is null.
+///
+/// # Safety
+/// `cache` must be null or a live handle; `key` must point to `key_len` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_get(
+    cache: *const DualCacheHandle,
+    key: *const u8,
+    key_len: usize,
+) -> DualCacheBytes {
+    // SAFETY: forwarded from this function's contract
+    let (handle, key) = unsafe { (handle(cache), bytes(key, key_len)) };
+    handle
+        .and_then(|handle| handle.cache.get(key))
+        .map_or_else(DualCacheBytes::empty, DualCacheBytes::from_vec)
+}
+
+/// Whether `key` is live in the mirror. Sends no promotion signal.
+///
+/// # Safety
+/// Same as `dualcache_get`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_contains(
+    cache: *const DualCacheHandle,
+    key: *const u8,
+    key_len: usize,
+) -> bool {
+    // SAFETY: forwarded from this function's contract
+    let (handle, key) = unsafe { (handle(cache), bytes(key, key_len)) };
+    handle.is_some_and(|handle| handle.cache.contains_key(key))
+}
+
+/// Inserts (or updates) `key`, visible to `dualcache_get` after the next commit.
+/// Returns false if `cache` is null.
+///
+/// # Safety
+/// `cache` must be null or a live handle; `key` / `value` must point to
+/// `key_len` / `value_len` readable bytes. Both are copied.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_insert(
+    cache: *const DualCacheHandle,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+    ttl_secs: u64,
+) -> bool {
+    // SAFETY: forwarded from this function's contract
+    let (handle, key, value) = unsafe { (handle(cache), bytes(key, key_len), bytes(value, value_len)) };
+    let Some(handle) = handle else { return false };
+    handle.cache.insert(key.to_vec(), value.to_vec(), ttl_secs);
+    true
+}
+
+/// Deletes `key`. Returns false if `cache` is null.
+///
+/// # Safety
+/// Same as `dualcache_get`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_delete(
+    cache: *const DualCacheHandle,
+    key: *const u8,
+    key_len: usize,
+) -> bool {
+    // SAFETY: forwarded from this function's contract
+    let (handle, key) = unsafe { (handle(cache), bytes(key, key_len)) };
+    let Some(handle) = handle else { return false };
+    handle.cache.delete(key);
+    true
+}
+
+/// Publishes `main` to the mirror; returns the visible generation (0 for null).
+///
+/// # Safety
+/// `cache` must be null or a live handle from `dualcache_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_commit(cache: *const DualCacheHandle) -> u64 {
+    // SAFETY: forwarded from this function's contract
+    unsafe { handle(cache) }.map_or(0, |handle| handle.cache.commit())
+}
+
+/// Runs the membrane update and housekeeping (see `DualCache::maintenance`).
+///
+/// # Safety
+/// `cache` must be null or a live handle from `dualcache_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_maintenance(cache: *const DualCacheHandle) {
+    // SAFETY: forwarded from this function's contract
+    if let Some(handle) = unsafe { handle(cache) } {
+        handle.cache.maintenance();
+    }
+}
+
+/// Approximate entry count of the mirror (0 for null).
+///
+/// # Safety
+/// `cache` must be null or a live handle from `dualcache_new`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_len(cache: *const DualCacheHandle) -> usize {
+    // SAFETY: forwarded from this function's contract
+    unsafe { handle(cache) }.map_or(0, |handle| handle.cache.len())
+}
+
+/// Releases a value returned by `dualcache_get`. Null is ignored.
+///
+/// # Safety
+/// `bytes` must come from `dualcache_get` and be freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn dualcache_bytes_free(bytes: DualCacheBytes) {
+    if bytes.ptr.is_null() {
+        return;
+    }
+    // SAFETY: `ptr` / `len` describe a boxed slice leaked by `from_vec`
+    drop(unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(bytes.ptr, bytes.len)) });
+}
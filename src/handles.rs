@@ -0,0 +1,213 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossbeam::channel::Receiver;
+
+use crate::{DaemonConfig, DaemonHandle, DualCache, DualCacheBuilder, Iter, LocalReader, Probe, Signal};
+
+// -----------------------------------------------------------------------------
+// Read / Write Handles (Left-Right Split)
+// -----------------------------------------------------------------------------
+
+/// Read-only view of a cache: mirror lookups plus lossy signaling.
+/// Cheap to clone and hand to every reader thread; it exposes nothing that
+/// takes the main lock. Lookups go through the mirror replica the handle is
+/// bound to (see `WriteHandle::replica_reader`).
+pub struct ReadHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V>>,
+    replica: usize,
+}
+
+/// The single writer of a cache. Not `Clone`; dereferences to `DualCache`
+/// for the full write, maintenance, and commit API.
+pub struct WriteHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V>>,
+}
+
+/// Output of `DualCacheBuilder::build_handles`: readers, the writer, and
+/// the signal receiver for the Daemon.
+pub type SplitHandles<K, V> = (ReadHandle<K, V>, WriteHandle<K, V>, Receiver<Signal<K>>);
+
+impl DualCacheBuilder {
+    /// Like `build`, but returns split handles instead of a shared `DualCache`.
+    pub fn build_handles<K, V>(self) -> SplitHandles<K, V>
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        let (cache, rx) = self.build();
+        let reader = ReadHandle {
+            cache: Arc::clone(&cache),
+            replica: 0,
+        };
+        (reader, WriteHandle { cache }, rx)
+    }
+}
+
+impl<K, V> Clone for ReadHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache: Arc::clone(&self.cache),
+            replica: self.replica,
+        }
+    }
+}
+
+impl<K, V> ReadHandle<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_with(key, V::clone)
+    }
+
+    pub fn get_with<Q, R>(&self, key: &Q, read: impl FnOnce(&V) -> R) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let snapshot = self.cache.replica(self.replica).load();
+        self.cache.read_in(&snapshot, key, |node| read(&node.value))
+    }
+
+    pub fn get_many<I>(&self, keys: I) -> Vec<Option<V>>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut results = Vec::new();
+        let snapshot = self.cache.replica(self.replica).load();
+        self.cache.read_many_in(&snapshot, keys, |_, value| results.push(value));
+        results
+    }
+
+    pub fn get_many_map<I>(&self, keys: I) -> HashMap<K, V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut results = HashMap::new();
+        let snapshot = self.cache.replica(self.replica).load();
+        self.cache.read_many_in(&snapshot, keys, |key, value| {
+            if let Some(value) = value {
+                results.insert(key, value);
+            }
+        });
+        results
+    }
+
+    pub fn peek<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.cache.probe(&self.cache.replica(self.replica).load(), key) {
+            Probe::Hit(node) | Probe::Stale(node) => Some(node.value.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let snapshot = self.cache.replica(self.replica).load();
+        matches!(self.cache.probe(&snapshot, key), Probe::Hit(_) | Probe::Stale(_))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    pub fn occupancy_ratio(&self) -> f64 {
+        self.cache.occupancy_ratio()
+    }
+
+    /// See `DualCache::iter`.
+    pub fn iter(&self) -> Iter<K, V> {
+        self.cache.iter()
+    }
+
+    /// See `DualCache::local_reader`; snapshots come from this handle's replica.
+    pub fn local_reader(&self) -> LocalReader<K, V> {
+        self.cache.local_reader_on(self.replica)
+    }
+
+    /// The mirror replica this handle reads from.
+    pub fn replica(&self) -> usize {
+        self.replica
+    }
+
+    pub fn current_generation(&self) -> u64 {
+        self.cache.current_generation()
+    }
+
+    pub fn wait_for_generation(&self, generation: u64) {
+        self.cache.wait_for_generation(generation);
+    }
+
+    pub fn wait_for_generation_timeout(&self, generation: u64, timeout: Duration) -> bool {
+        self.cache.wait_for_generation_timeout(generation, timeout)
+    }
+}
+
+impl<K, V> WriteHandle<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// A new reader for the same cache.
+    pub fn reader(&self) -> ReadHandle<K, V> {
+        self.replica_reader(0)
+    }
+
+    /// A new reader bound to mirror replica `replica` (taken modulo
+    /// `mirror_replicas`), e.g. one replica per NUMA node.
+    pub fn replica_reader(&self, replica: usize) -> ReadHandle<K, V> {
+        ReadHandle {
+            cache: Arc::clone(&self.cache),
+            replica: replica % self.cache.mirror_replicas(),
+        }
+    }
+
+    /// Spawns the built-in daemon (see `DualCache::spawn_daemon`).
+    pub fn spawn_daemon(&self, rx: Receiver<Signal<K>>, config: DaemonConfig) -> DaemonHandle {
+        self.cache.spawn_daemon(rx, config)
+    }
+}
+
+impl<K, V> Deref for WriteHandle<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    type Target = DualCache<K, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cache
+    }
+}
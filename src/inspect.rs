@@ -0,0 +1,120 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::time::Duration;
+
+use crate::{Cache, DualCache, Probe};
+
+// -----------------------------------------------------------------------------
+// Ranking Introspection (Mirror Snapshot)
+// -----------------------------------------------------------------------------
+
+/// A value together with its ranking metadata, as seen by the mirror.
+/// Returned by `DualCache::get_entry`; timestamps are clock milliseconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryView<V> {
+    pub value: V,
+    pub counter: u64,
+    /// Arena index; 0 is the hottest slot.
+    pub rank: usize,
+    pub inserted_at: u64,
+    pub expires_at: u64,
+    /// Time until `expires_at` (`Duration::MAX` for pinned or immortal entries).
+    pub ttl_remaining: Duration,
+    /// Whether the entry ranks above the membrane (`rank < evict_point`).
+    pub in_safe_zone: bool,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Up to `n` live entries from the top of the ranking, as
+    /// `(key, counter, rank)` with rank 0 first. Reads the mirror; no signals.
+    pub fn hottest(&self, n: usize) -> Vec<(K, u64, usize)> {
+        let snapshot = self.mirror.load();
+        snapshot.ranked(0..snapshot.arena.len(), n)
+    }
+
+    /// Up to `n` live entries from the bottom of the ranking (coldest first),
+    /// i.e. the nodes next in line for the cliff edge.
+    pub fn coldest(&self, n: usize) -> Vec<(K, u64, usize)> {
+        let snapshot = self.mirror.load();
+        snapshot.ranked((0..snapshot.arena.len()).rev(), n)
+    }
+
+    /// Arena index (rank) of `key` in the mirror; 0 is the hottest slot.
+    pub fn rank_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.mirror.load().position(key)
+    }
+
+    /// `key`'s value plus counter, rank, timestamps and membrane side, for
+    /// debugging endpoints. Reads the mirror and sends no signal.
+    pub fn get_entry<Q>(&self, key: &Q) -> Option<EntryView<V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let snapshot = self.mirror.load();
+        let (Probe::Hit(node) | Probe::Stale(node)) = self.probe(&snapshot, key) else {
+            return None;
+        };
+        let rank = snapshot.position(key)?;
+        let ttl_remaining = if node.pinned || node.time_stamp == u64::MAX {
+            Duration::MAX
+        } else {
+            Duration::from_millis(node.time_stamp.saturating_sub(snapshot.current_time()))
+        };
+
+        Some(EntryView {
+            value: node.value.clone(),
+            counter: snapshot.frequency(rank),
+            rank,
+            inserted_at: node.inserted_at,
+            expires_at: node.time_stamp,
+            ttl_remaining,
+            in_safe_zone: rank < snapshot.evict_point,
+        })
+    }
+
+    /// Membrane position in the mirror. Ranks below it survive the cliff edge.
+    pub fn evict_point(&self) -> usize {
+        self.mirror.load().evict_point
+    }
+
+    /// Whether `key` currently ranks above the membrane (protected from the
+    /// next cliff-edge truncation). False for absent keys.
+    pub fn is_in_safe_zone<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let snapshot = self.mirror.load();
+        snapshot
+            .position(key)
+            .is_some_and(|rank| rank < snapshot.evict_point)
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: First `n` live slots of `ranks`, skipping garbage
+    fn ranked(&self, ranks: impl Iterator<Item = usize>, n: usize) -> Vec<(K, u64, usize)> {
+        ranks
+            .filter(|&rank| self.is_live(rank))
+            .take(n)
+            .map(|rank| {
+                (self.arena[rank].key.clone(), self.frequency(rank), rank)
+            })
+            .collect()
+    }
+}
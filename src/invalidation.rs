@@ -0,0 +1,78 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Distributed Invalidation (Publish / Subscribe)
+// -----------------------------------------------------------------------------
+
+/// Receives keys invalidated by other nodes (see `InvalidationBus::subscribe`).
+pub type InvalidationHandler<K> = Box<dyn Fn(K) + Send + Sync>;
+
+/// Transport that keeps replicas of a cache on several nodes coherent (e.g. a
+/// Redis channel or a message broker). Once attached with
+/// `set_invalidation_bus`, every local update and delete publishes the key;
+/// keys received from other nodes are deleted locally, without re-publishing.
+/// Fresh inserts of absent keys are not published, so filling on a miss does
+/// not knock the entry out of the other replicas.
+pub trait InvalidationBus<K>: Send + Sync {
+    /// Announces that `key` was updated or deleted on this node. Runs under the
+    /// main lock: hand the key off (queue, non-blocking send) and never call
+    /// back into this cache synchronously. Do not echo a node's own
+    /// invalidations back to it.
+    fn publish(&self, key: &K);
+
+    /// Registers the handler for invalidations from other nodes. Called once
+    /// per `set_invalidation_bus`.
+    fn subscribe(&self, handler: InvalidationHandler<K>);
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Attaches `bus` (replacing any previous one) and subscribes to it.
+    /// The subscription only holds a weak reference to the cache. Received
+    /// invalidations follow the `CommitPolicy` like other writes.
+    pub fn set_invalidation_bus(self: &Arc<Self>, bus: Arc<dyn InvalidationBus<K>>) {
+        let cache = Arc::downgrade(self);
+        bus.subscribe(Box::new(move |key| {
+            if let Some(cache) = cache.upgrade() {
+                cache.delete_local(&key);
+            }
+        }));
+        self.main.lock().invalidation = Some(bus);
+    }
+
+    /// Deletes `key` without publishing an invalidation (what received
+    /// invalidations do).
+    pub fn delete_local<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            let bus = cache.invalidation.take();
+            cache.double_swap_delete(key);
+            cache.invalidation = bus;
+        });
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Tells the other nodes `key` changed here
+    pub(crate) fn publish_invalidation(&self, key: &K) {
+        if let Some(bus) = &self.invalidation {
+            bus.publish(key);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Snapshot Iteration (Mirror)
+// -----------------------------------------------------------------------------
+
+/// Rank-ordered iterator over one mirror snapshot, yielding
+/// `(key, value, counter, expires_at)` with `expires_at` in clock
+/// milliseconds. Obtained via `DualCache::iter`.
+///
+/// The snapshot is pinned for the iterator's lifetime: commits made meanwhile
+/// are not observed, and writers are never blocked.
+pub struct Iter<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    snapshot: Arc<Cache<K, V, S>>,
+    rank: usize,
+    now: u64,
+}
+
+impl<K, V, S> Iterator for Iter<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    type Item = (K, V, u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.rank < self.snapshot.arena.len() {
+            let rank = self.rank;
+            self.rank += 1;
+
+            // Skip garbage and entries a reader would treat as expired
+            let node = &self.snapshot.arena[rank];
+            if self.snapshot.is_live(rank) && !node.is_expired(self.now) {
+                let counter = self.snapshot.frequency(rank);
+                return Some((node.key.clone(), node.value.clone(), counter, node.time_stamp));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.snapshot.arena.len() - self.rank))
+    }
+}
+
+impl<K, V, S> FusedIterator for Iter<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Live entries of the current mirror in rank order (hottest first).
+    /// Lock-free and signal-free; see `Iter`.
+    pub fn iter(&self) -> Iter<K, V, S> {
+        let snapshot = self.mirror.load_full();
+        let now = snapshot.current_time();
+        Iter {
+            snapshot,
+            rank: 0,
+            now,
+        }
+    }
+
+    /// Keys of `iter`, in rank order.
+    pub fn keys(&self) -> impl Iterator<Item = K> + use<K, V, S> {
+        self.iter().map(|(key, ..)| key)
+    }
+
+    /// Values of `iter`, in rank order.
+    pub fn values(&self) -> impl Iterator<Item = V> + use<K, V, S> {
+        self.iter().map(|(_, value, ..)| value)
+    }
+}
@@ -1,42 +1,821 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::sync::Arc;
-use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use parking_lot::{Condvar, Mutex};
 use arc_swap::ArcSwap;
-use std::collections::HashMap;
-use std::hash::Hash;
-use crossbeam::channel::{Sender, Receiver, bounded};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use crossbeam::channel::{Sender, Receiver, TrySendError, bounded, unbounded};
+use crossbeam::utils::CachePadded;
+use std::ops::Range;
+use std::time::Duration;
+
+#[cfg(feature = "admin")]
+mod admin;
+#[cfg(feature = "async")]
+mod async_cache;
+mod ban;
+mod batch;
+#[cfg(feature = "cached")]
+mod cached_compat;
+#[cfg(feature = "disk")]
+mod checkpoint;
+mod clear;
+mod clock;
+mod coalesce;
+mod command;
+mod daemon;
+mod demote;
+mod dropper;
+#[cfg(feature = "disk")]
+mod disk;
+mod entry;
+mod events;
+mod expiry;
+mod fallible;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod handles;
+mod inspect;
+mod invalidation;
+mod iter;
+mod listener;
+mod local;
+mod loading;
+mod membrane;
+mod memory;
+mod merge;
+mod namespaced;
+#[cfg(feature = "tower")]
+mod middleware;
+pub mod moka;
+#[cfg(any(feature = "metrics", feature = "prometheus"))]
+mod metrics;
+#[cfg(feature = "disk")]
+mod oplog;
+#[cfg(feature = "serde")]
+mod persist;
+#[cfg(feature = "python")]
+mod python;
+mod priority;
+mod refresh;
+mod pinned;
+mod segmented;
+#[cfg(feature = "server")]
+mod server;
+mod shadow;
+mod split;
+mod sharded;
+#[cfg(feature = "simulate")]
+mod simulate;
+mod sketch;
+mod stats;
+mod store;
+mod tags;
+mod time;
+mod ttl;
+mod unsync;
+mod validate;
+mod version;
+mod warm;
+mod watch;
+
+#[cfg(feature = "axum")]
+pub use admin::router as admin_router;
+#[cfg(feature = "admin")]
+pub use admin::{AdminHandler, AdminResponse, EvictPointSample};
+#[cfg(feature = "async")]
+pub use async_cache::AsyncDaemonHandle;
+pub use batch::BatchWriter;
+#[cfg(feature = "disk")]
+pub use checkpoint::{CheckpointInfo, Checkpointer};
+pub use clock::{Clock, MockClock, MonotonicClock, SystemClock};
+use coalesce::ReadCoalescer;
+pub use command::CacheCommand;
+pub use daemon::{DaemonConfig, DaemonHandle};
+#[cfg(feature = "disk")]
+pub use disk::{DiskTier, TieredDualCache};
+pub use entry::{Entry, OccupiedEntry, OccupiedError, VacantEntry};
+pub use events::CacheEvent;
+pub use handles::{ReadHandle, SplitHandles, WriteHandle};
+pub use inspect::EntryView;
+pub use invalidation::{InvalidationBus, InvalidationHandler};
+pub use iter::Iter;
+pub use listener::CacheListener;
+pub use local::LocalReader;
+#[cfg(feature = "async")]
+pub use loading::AsyncLoader;
+pub use loading::{Loader, LoadingDualCache};
+pub use membrane::{EvictPointPolicy, FixedRatio, HitRateMembrane, MembraneView, ViscousMembrane};
+pub use memory::{MemSize, MemoryCallback};
+pub use merge::MergeStrategy;
+pub use namespaced::{NamespaceError, NamespacedDualCache};
+use memory::MemoryAlarm;
+#[cfg(feature = "tower")]
+pub use middleware::{CacheLayer, CachePolicy, CacheService};
+#[cfg(feature = "disk")]
+pub use oplog::OpLog;
+#[cfg(feature = "serde")]
+pub use persist::SnapshotError;
+#[cfg(feature = "python")]
+pub use python::{PyDualCache, PyKey};
+pub use priority::Priority;
+pub use refresh::Refresher;
+use segmented::{SegmentedVec, ShardedIndex};
+#[cfg(feature = "server")]
+pub use server::RespServer;
+pub use shadow::{ShadowDualCache, ShadowReport};
+pub use sharded::ShardedDualCache;
+#[cfg(feature = "simulate")]
+pub use simulate::{Baseline, SimulationReport, SimulationSample, Simulator, TraceOp};
+pub use stats::CacheStats;
+pub use store::{BackingStore, StoredDualCache, WriteMode};
+pub use ttl::Ttl;
+pub use unsync::LocalDualCache;
+pub use validate::{ValidationReport, Violation};
+pub use version::CasError;
+pub use watch::CommitWatch;
+use ban::Bans;
+use dropper::{Dropper, Garbage};
+use expiry::ExpiryWheel;
+use sketch::FrequencySketch;
+use time::Instant;
+use stats::ReadCounters;
 
 // -----------------------------------------------------------------------------
 // 1. Data Structures (Immutable Contract)
 // -----------------------------------------------------------------------------
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node<K, V> {
     pub key: K, 
     pub value: V, 
     pub counter: u64, 
+    /// Absolute deadline in clock milliseconds.
     pub time_stamp: u64, 
+    /// Time-to-idle window in milliseconds (0 = disabled).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tti: u64,
+    /// Last time a read signal reached this node (refreshes the idle window).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub last_access: u64,
+    /// Tags attached by `insert_tagged` (see `invalidate_tag`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// Pinned nodes never expire and are kept across cliff-edge truncation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pinned: bool,
+    /// Soft TTL in milliseconds (0 = disabled). Past `soft_deadline` reads still
+    /// hit but ask the Daemon to refresh the value (`Signal::Refresh`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soft_ttl: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soft_deadline: u64,
+    /// When the key was first inserted (updates keep it).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inserted_at: u64,
+    /// Starts at 0 and is bumped by every value update (see `compare_and_update`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: u64,
+    /// Eviction class (see `insert_with_priority`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority: Priority,
 }
 
-#[derive(Clone)] // Derived to support Deep Clone for sync_mirror
-struct Cache<K, V>
+impl<K, V> Node<K, V> {
+    /// A plain node (no TTI, tags, pin, soft TTL or priority), e.g. for `warm_from`.
+    /// `time_stamp` is the absolute deadline in clock milliseconds.
+    pub fn new(key: K, value: V, counter: u64, time_stamp: u64) -> Self {
+        Self {
+            key,
+            value,
+            counter,
+            time_stamp,
+            tti: 0,
+            last_access: 0,
+            tags: Vec::new(),
+            pinned: false,
+            soft_ttl: 0,
+            soft_deadline: 0,
+            inserted_at: 0,
+            version: 0,
+            priority: Priority::Normal,
+        }
+    }
+
+    /// Whether the node is past its TTL or has been idle longer than its TTI.
+    pub fn is_expired(&self, now: u64) -> bool {
+        if self.pinned {
+            return false;
+        }
+        now > self.time_stamp || (self.tti > 0 && now > self.last_access.saturating_add(self.tti))
+    }
+
+    /// Whether the node is past its soft TTL (stale but still served).
+    pub fn is_stale(&self, now: u64) -> bool {
+        self.soft_ttl > 0 && now > self.soft_deadline
+    }
+}
+
+// Helper: Second-based public TTLs to the millisecond deadlines nodes store
+pub(crate) fn secs_to_millis(secs: u64) -> u64 {
+    secs.saturating_mul(1_000)
+}
+
+// Helper: Merges repeated keys of a read batch into per-key hit counts
+fn count_reads<K: Hash + Eq>(keys: impl IntoIterator<Item = K>) -> HashMap<K, u64> {
+    let mut hits = HashMap::new();
+    for key in keys {
+        *hits.entry(key).or_default() += 1;
+    }
+    hits
+}
+
+// Clone is cheap: arena and index are Arc-segmented (structural sharing),
+// so sync_mirror copies segment pointers rather than nodes.
+#[derive(Clone)]
+struct Cache<K, V, S = RandomState>
 where
     K: Hash + Eq + Clone,
 {
-    arena: Vec<Node<K, V>>, 
-    index: HashMap<K, usize>, 
+    arena: SegmentedVec<Node<K, V>>, 
+    index: ShardedIndex<K, S>, 
     counter_sum: u64, 
     evict_point: usize, 
+    // Evict point the builder configured, restored by `clear`
+    base_evict_point: usize,
     capacity: usize,
+    step_ratio: f64,
+    // Write-path statistics (guarded by the main lock)
+    insertions: u64,
+    evictions: u64,
+    expirations: u64,
+    // Set once any node carries a TTI, enabling the idle purge in maintenance
+    has_idle_entries: bool,
+    // Set once any node gets a non-Normal priority, enabling victim reordering at truncation
+    has_priorities: bool,
+    // Commit bookkeeping for CommitPolicy
+    pending_writes: u64,
+    last_commit: Instant,
+    // Set by any change since the last commit; a clean commit is a no-op
+    dirty: bool,
+    listener: Option<Arc<dyn CacheListener<K, V>>>,
+    // Change-event subscribers (see DualCache::subscribe)
+    events: Option<Arc<events::EventBus<K>>>,
+    // Keys streamed by `watch_key`
+    watches: Option<Arc<watch::KeyWatches<K, V>>>,
+    // Peers told about local updates and deletes
+    invalidation: Option<Arc<dyn InvalidationBus<K>>>,
+    // Write-ahead log appended by inserts, updates and deletes
+    #[cfg(feature = "disk")]
+    oplog: Option<Arc<dyn oplog::WriteLog<K, V>>>,
+    // Weight-aware capacity (see DualCache::set_weigher)
+    weigher: Option<Weigher<K, V>>,
+    max_weight: u64,
+    total_weight: u64,
+    // Membrane dynamics; stateful, so shared (not copied) with the mirror
+    membrane: Arc<Mutex<Box<dyn EvictPointPolicy>>>,
+    // Positions a node climbs per applied hit
+    climb: ClimbDistance,
+    // Counter aging applied by maintenance
+    decay: CounterDecay,
+    decay_interval: Duration,
+    last_decay: Instant,
+    // Upper bound on unlinked slots still occupying the arena
+    garbage: usize,
+    // Slab free list: slots unlinked by expiry, reused in place by inserts.
+    // Lazily validated like the index (a swap or truncation may invalidate an entry)
+    free: Vec<usize>,
+    // Time source for TTL / TTI deadlines
+    clock: Arc<dyn Clock>,
+    // Reloads stale (soft-expired) entries on Signal::Refresh
+    refresher: Option<Refresher<K, V>>,
+    // (window, ttl) for refresh-ahead in maintenance (see `DualCache::set_refresh_ahead`)
+    refresh_ahead: Option<(u64, Ttl)>,
+    // Bumped by every commit; the mirror carries the generation it was cut at
+    generation: u64,
+    // Live pinned nodes; truncation only looks for them when non-zero
+    pinned_count: usize,
+    // Hits a probation node needs before crossing the membrane (0 = off)
+    promotion_hits: u64,
+    // TinyLFU doorkeeper; shared (not copied) with the mirror like the membrane
+    admission: Option<Arc<Mutex<FrequencySketch>>>,
+    rejections: u64,
+    // Sketch frequency mode: replaces `Node::counter` (same sketch as `admission` if both are on)
+    frequency: Option<Arc<Mutex<FrequencySketch>>>,
+    // Sketch resets already folded into `counter_sum` (each one halves the estimates)
+    sketch_resets: u64,
+    // Deadline buckets for proactive expiry (see `DualCacheBuilder::expiry_wheel`)
+    expiry: Option<Arc<Mutex<ExpiryWheel<K>>>>,
+    // (high, low) fractions of capacity for maintenance shedding (see `DualCacheBuilder::watermarks`)
+    watermarks: Option<(f64, f64)>,
+    // Background dropper for truncated nodes (see `DualCacheBuilder::deferred_drop`)
+    dropper: Option<Dropper<CacheGarbage<K, V, S>>>,
+    // Threshold callback checked by maintenance (see `DualCache::set_memory_alarm`)
+    memory_alarm: Option<MemoryAlarm<K, V>>,
+    // Quarantined keys (see `DualCache::ban`); created on the first ban
+    bans: Option<Bans<K>>,
+    // Fraction by which `new_node` randomizes TTLs (0 = exact)
+    ttl_jitter: f64,
+}
+
+/// Aging applied to every counter so the ranking reflects recent popularity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CounterDecay {
+    /// Counters only ever increase (original behavior).
+    #[default]
+    Off,
+    /// Halve every counter.
+    Halve,
+    /// Subtract a fixed delta from every counter (saturating at 0).
+    Subtract(u64),
+}
+
+/// How far a node climbs toward rank 0 when its read signals are applied.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClimbDistance {
+    /// `n` positions per hit (default: 1; 0 freezes the ranking).
+    Fixed(usize),
+    /// `log2(rank) + 1` positions per hit: far from the top a node jumps
+    /// further, near the top it settles one step at a time.
+    Logarithmic,
+    /// `ratio` positions per unit the node's counter leads the node right
+    /// above it, at least one per hit: a sudden hot spot closes the gap fast.
+    Proportional(f64),
 }
 
-pub struct DualCache<K, V>
+impl Default for ClimbDistance {
+    fn default() -> Self {
+        Self::Fixed(1)
+    }
+}
+
+/// Cost function for weight-based capacity (e.g. approximate bytes per entry).
+/// Must be deterministic: a node is re-weighed when it leaves the cache.
+pub type Weigher<K, V> = Arc<dyn Fn(&K, &V) -> u64 + Send + Sync>;
+
+// A published snapshot slot (the primary mirror or one of its replicas)
+type Mirror<K, V, S> = ArcSwap<Cache<K, V, S>>;
+
+// What `Cache::discard` hands to the dropper thread
+type CacheGarbage<K, V, S> = Garbage<Node<K, V>, Cache<K, V, S>>;
+
+/// `S` hashes keys for the index (and therefore every read); swap in a
+/// faster `BuildHasher` for short keys via `DualCacheBuilder::build_with_hasher`.
+pub struct DualCache<K, V, S = RandomState>
 where
     K: Hash + Eq + Clone,
 {
-    main: Mutex<Cache<K, V>>, 
-    mirror: ArcSwap<Cache<K, V>>,
-    lazy_tx: Sender<K>,
+    main: Mutex<Cache<K, V, S>>, 
+    mirror: ArcSwap<Cache<K, V, S>>,
+    // Extra mirrors (replicas 1..n), published together with `mirror`
+    replicas: Box<[CachePadded<Mirror<K, V, S>>]>,
+    lazy_tx: Sender<Signal<K>>,
+    // Consumer-side handle used only by `SignalPolicy::DropOldest`
+    lazy_rx: Option<Receiver<Signal<K>>>,
+    signal_policy: SignalPolicy,
+    default_ttl: u64,
+    read_ttl_check: bool,
+    expiry_signals: bool,
+    counters: ReadCounters,
+    coalescer: Option<ReadCoalescer<K>>,
+    commit_policy: CommitPolicy,
+    // Lossless write commands, applied by the Daemon (see CacheCommand)
+    command_tx: Sender<CacheCommand<K, V>>,
+    command_rx: Receiver<CacheCommand<K, V>>,
+    // Latest published generation, for wait_for_generation
+    committed: Mutex<u64>,
+    // Same generation, readable without touching the ArcSwap (see LocalReader)
+    published: AtomicU64,
+    commit_cv: Condvar,
+    #[cfg(feature = "async")]
+    commit_notify: tokio::sync::Notify,
+    // Latest generation for `watch_commits_async` receivers
+    #[cfg(feature = "async")]
+    commit_watch: tokio::sync::watch::Sender<u64>,
+    #[cfg(feature = "async")]
+    in_flight: async_cache::InFlight<K, V>,
+    // Error caching for `get_or_try_insert_with` (0 = off)
+    error_ttl: u64,
+    errors: fallible::ErrorSlots<K>,
+}
+
+/// When writes are published to the mirror without an explicit `commit()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommitPolicy {
+    /// Only `commit()` (or the Daemon) refreshes the mirror.
+    #[default]
+    Manual,
+    /// Commit after every `n` writes (insert / update / delete).
+    EveryNWrites(u64),
+    /// Commit on the first write once this much time has passed since the last commit.
+    /// Writes followed by silence are published by the Daemon's `commit_interval`.
+    Interval(Duration),
+}
+
+/// Backpressure applied when the signal channel is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignalPolicy {
+    /// Discard the new signal (original lossy behavior).
+    #[default]
+    DropNewest,
+    /// Discard the oldest queued signal to make room for the new one.
+    DropOldest,
+    /// Only send roughly 1 in `n` hit signals; a full channel drops the newest.
+    /// Expiry signals are never sampled.
+    Sample(u32),
+    /// Block the reader for up to the given timeout, then drop the signal.
+    Block(Duration),
+    /// No queue: the reading thread applies the signal to `main` itself, so
+    /// no Daemon is needed (e.g. single-threaded wasm). Reads pay for the
+    /// climb and a soft-TTL refresh runs the loader inline. A signal raised
+    /// while any thread holds the main lock (a concurrent write, or a read
+    /// from inside a write) is dropped and counted in `dropped_signals`, so
+    /// under write contention reads stop promoting; use a channel policy
+    /// and a Daemon there.
+    Inline,
+}
+
+/// Messages sent from the read path to the Daemon over the lossy channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Signal<K> {
+    /// A hit: bump the counter and climb.
+    Read(K),
+    /// A reader observed the entry expired (TTL or idle window).
+    Expired(K),
+    /// Coalesced hits from one `get_many` call, applied under one lock.
+    ReadBatch(Vec<K>),
+    /// Aggregated `(key, hits)` pairs flushed by read coalescing.
+    ReadCounts(Vec<(K, u64)>),
+    /// A hit on an entry past its soft TTL: climb, and reload it through the
+    /// refresher (see `DualCache::set_refresher`).
+    Refresh(K),
+}
+
+// Outcome of validating a key against a mirror snapshot
+enum Probe<'a, K, V> {
+    Hit(&'a Node<K, V>),
+    // Past the soft TTL: served, but due for a refresh
+    Stale(&'a Node<K, V>),
+    Expired(&'a Node<K, V>),
+    Miss,
+}
+
+// -----------------------------------------------------------------------------
+// 1.1 Configuration (Builder)
+// -----------------------------------------------------------------------------
+
+// A freshly built cache and the receiving end of its signal channel
+type Built<K, V, S> = (Arc<DualCache<K, V, S>>, Receiver<Signal<K>>);
+
+/// Tuning knobs applied before the cache is constructed.
+/// Obtained via `DualCache::builder(capacity)`.
+#[derive(Clone, Debug)]
+pub struct DualCacheBuilder {
+    capacity: usize,
+    channel_capacity: usize,
+    default_ttl: u64,
+    evict_point: Option<usize>,
+    step_ratio: f64,
+    read_ttl_check: bool,
+    expiry_signals: bool,
+    commit_policy: CommitPolicy,
+    decay: CounterDecay,
+    climb: ClimbDistance,
+    decay_interval: Duration,
+    coalesce_threshold: usize,
+    signal_policy: SignalPolicy,
+    clock: Arc<dyn Clock>,
+    promotion_hits: u64,
+    admission_filter: bool,
+    frequency_sketch: bool,
+    mirror_replicas: usize,
+    expiry_wheel: bool,
+    watermarks: Option<(f64, f64)>,
+    deferred_drop: bool,
+    ttl_jitter: f64,
+    error_ttl: Duration,
+}
+
+impl DualCacheBuilder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            channel_capacity: 10_000,
+            default_ttl: 3_600,
+            evict_point: None, // Defaults to capacity per spec
+            step_ratio: 0.1,   // capacity / 10
+            read_ttl_check: true,
+            expiry_signals: true,
+            commit_policy: CommitPolicy::Manual,
+            decay: CounterDecay::Off,
+            climb: ClimbDistance::default(),
+            decay_interval: Duration::from_secs(60),
+            coalesce_threshold: 0,
+            signal_policy: SignalPolicy::DropNewest,
+            clock: Arc::new(SystemClock),
+            promotion_hits: 0,
+            admission_filter: false,
+            frequency_sketch: false,
+            mirror_replicas: 1,
+            expiry_wheel: false,
+            watermarks: None,
+            deferred_drop: false,
+            ttl_jitter: 0.0,
+            error_ttl: Duration::ZERO,
+        }
+    }
+
+    /// Bound of the lossy read-signal channel.
+    pub fn channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity;
+        self
+    }
+
+    /// TTL (seconds) used by `DualCache::put`.
+    pub fn default_ttl(mut self, ttl_secs: u64) -> Self {
+        self.default_ttl = ttl_secs;
+        self
+    }
+
+    /// Initial position of the membrane. Clamped to `capacity`.
+    pub fn evict_point(mut self, evict_point: usize) -> Self {
+        self.evict_point = Some(evict_point);
+        self
+    }
+
+    /// Membrane step as a fraction of capacity (0.1 == `capacity / 10`).
+    pub fn membrane_step_ratio(mut self, ratio: f64) -> Self {
+        self.step_ratio = ratio;
+        self
+    }
+
+    /// Whether `get` treats entries past their `time_stamp` as misses.
+    /// Disabling skips the clock read on every hit.
+    pub fn read_ttl_check(mut self, enabled: bool) -> Self {
+        self.read_ttl_check = enabled;
+        self
+    }
+
+    /// Whether readers report expired hits to the Daemon (`Signal::Expired`)
+    /// so the node is invalidated without waiting for a climb.
+    pub fn expiry_signals(mut self, enabled: bool) -> Self {
+        self.expiry_signals = enabled;
+        self
+    }
+
+    /// Automatic commit policy applied by the write methods.
+    pub fn commit_policy(mut self, policy: CommitPolicy) -> Self {
+        self.commit_policy = policy;
+        self
+    }
+
+    /// Counter aging run by `maintenance` at most once per `interval`.
+    /// Decay touches every node, so the following commit copies every segment.
+    pub fn counter_decay(mut self, decay: CounterDecay, interval: Duration) -> Self {
+        self.decay = decay;
+        self.decay_interval = interval;
+        self
+    }
+
+    /// Positions a node climbs per applied hit (default `ClimbDistance::Fixed(1)`).
+    pub fn climb_distance(mut self, climb: ClimbDistance) -> Self {
+        self.climb = climb;
+        self
+    }
+
+    /// Aggregates read hits into per-thread `(key, count)` maps instead of
+    /// sending one signal per hit. A map is sent as one `Signal::ReadCounts`
+    /// once it holds `flush_threshold` distinct keys; the rest is applied by
+    /// `flush_read_signals` (run by the Daemon before each commit).
+    /// `0` disables coalescing (the default).
+    pub fn read_coalescing(mut self, flush_threshold: usize) -> Self {
+        self.coalesce_threshold = flush_threshold;
+        self
+    }
+
+    /// What the read path does when the signal channel is full.
+    pub fn signal_policy(mut self, policy: SignalPolicy) -> Self {
+        self.signal_policy = policy;
+        self
+    }
+
+    /// Time source for expiry. Defaults to `SystemClock`; use
+    /// `MonotonicClock` to ignore wall-clock jumps or `MockClock` in tests.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Segmented (SLRU-style) mode: ranks below the membrane form a probation
+    /// region where inserts land, and a node only climbs across the membrane
+    /// into the protected region after `hits` read hits. Nodes it displaces
+    /// drop back into probation. `0` disables the gate (the default).
+    pub fn probation_hits(mut self, hits: u64) -> Self {
+        self.promotion_hits = hits;
+        self
+    }
+
+    /// TinyLFU-style admission: a frequency sketch of recent inserts and hits
+    /// decides whether a new key may trigger the cliff edge once the cache is
+    /// full. Keys seen less often than the strongest node of the victim region
+    /// (the slot at `evict_point`) are not inserted.
+    pub fn admission_filter(mut self, enabled: bool) -> Self {
+        self.admission_filter = enabled;
+        self
+    }
+
+    /// Sketch frequency mode: popularity lives in one shared count-min sketch
+    /// (halved every ~10 x capacity increments); `Node::counter` stays 0 and
+    /// is ignored. Climbs, the probation gate, the membrane and
+    /// `EntryView::counter` read the sketch estimate, which saturates at 15,
+    /// so keep `probation_hits` below that. The membrane keeps a running total
+    /// of the estimates, recomputed whenever the sketch halves. Shares the
+    /// admission sketch when `admission_filter` is on as well.
+    pub fn frequency_sketch(mut self, enabled: bool) -> Self {
+        self.frequency_sketch = enabled;
+        self
+    }
+
+    /// Files every TTL deadline on a wheel of one-second buckets, so
+    /// `maintenance` expires entries nobody reads, freeing their slots,
+    /// instead of leaving expiry to the read path. Costs one key clone per
+    /// insert (and per shortened deadline).
+    pub fn expiry_wheel(mut self, enabled: bool) -> Self {
+        self.expiry_wheel = enabled;
+        self
+    }
+
+    /// Sheds load ahead of the cliff edge: once `maintenance` finds the
+    /// arena at `high` (a fraction of capacity), it evicts the coldest
+    /// entries until `low` remain, so eviction is spread over maintenance
+    /// runs instead of landing on one insert. Pinned entries are kept.
+    /// Both are clamped to `0.0..=1.0`; `low` is capped at `high`.
+    pub fn watermarks(mut self, high: f64, low: f64) -> Self {
+        let high = high.clamp(0.0, 1.0);
+        self.watermarks = Some((high, low.clamp(0.0, high)));
+        self
+    }
+
+    /// Moves nodes dropped by cliff-edge truncation (and garbage sweeps) to
+    /// a background thread that frees them, so evicting thousands of large
+    /// values does not run their destructors under the main lock. Mirror
+    /// snapshots replaced by a commit go there too, as they may hold the
+    /// last reference to nodes truncated since.
+    /// Ignored by `build_local`.
+    pub fn deferred_drop(mut self, enabled: bool) -> Self {
+        self.deferred_drop = enabled;
+        self
+    }
+
+    /// Spreads expiry: every TTL given in seconds is randomly stretched or
+    /// shrunk by up to `ratio` (0.1 = ±10%), so entries written together do
+    /// not all expire, and hit the origin, in the same second. Clamped to
+    /// `0.0..=1.0`; 0 (the default) keeps TTLs exact.
+    pub fn ttl_jitter(mut self, ratio: f64) -> Self {
+        self.ttl_jitter = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// How long `get_or_try_insert_with` remembers an error from its init
+    /// closure and returns it instead of retrying (default zero: never cached).
+    pub fn error_ttl(mut self, ttl: Duration) -> Self {
+        self.error_ttl = ttl;
+        self
+    }
+
+    /// Number of independent mirrors `commit` publishes to (default 1).
+    /// Each replica has its own `ArcSwap` on its own cache line, so reader
+    /// groups bound to different replicas (e.g. one per NUMA node, see
+    /// `WriteHandle::replica_reader`) do not contend on one pointer.
+    /// All replicas share the same snapshot; only the swap slot is duplicated.
+    pub fn mirror_replicas(mut self, replicas: usize) -> Self {
+        self.mirror_replicas = replicas.max(1);
+        self
+    }
+
+    pub fn build<K, V>(self) -> (Arc<DualCache<K, V>>, Receiver<Signal<K>>)
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        self.build_with_hasher(RandomState::new())
+    }
+
+    /// Like `build`, but the key index hashes with `hasher` instead of SipHash
+    /// (e.g. an ahash or FxHash builder for small integer keys).
+    pub fn build_with_hasher<K, V, S>(
+        self,
+        hasher: S,
+    ) -> Built<K, V, S>
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+        S: BuildHasher + Clone + Send + Sync + 'static,
+    {
+        let (tx, rx) = bounded(self.channel_capacity);
+        let (command_tx, command_rx) = unbounded();
+
+        let mut initial_cache = self.new_cache(hasher);
+        if self.deferred_drop {
+            initial_cache.dropper = Some(Dropper::spawn());
+        }
+
+        let dual_cache = Arc::new(DualCache {
+            main: Mutex::new(initial_cache.clone()),
+            replicas: (1..self.mirror_replicas)
+                .map(|_| CachePadded::new(ArcSwap::from_pointee(initial_cache.clone())))
+                .collect(),
+            mirror: ArcSwap::from_pointee(initial_cache),
+            lazy_tx: tx,
+            lazy_rx: (self.signal_policy == SignalPolicy::DropOldest).then(|| rx.clone()),
+            signal_policy: self.signal_policy,
+            default_ttl: self.default_ttl,
+            read_ttl_check: self.read_ttl_check,
+            expiry_signals: self.expiry_signals,
+            counters: ReadCounters::new(),
+            coalescer: (self.coalesce_threshold > 0)
+                .then(|| ReadCoalescer::new(self.coalesce_threshold)),
+            commit_policy: self.commit_policy,
+            command_tx,
+            command_rx,
+            committed: Mutex::new(0),
+            published: AtomicU64::new(0),
+            commit_cv: Condvar::new(),
+            #[cfg(feature = "async")]
+            commit_notify: tokio::sync::Notify::new(),
+            #[cfg(feature = "async")]
+            commit_watch: tokio::sync::watch::Sender::new(0),
+            #[cfg(feature = "async")]
+            in_flight: Mutex::new(std::collections::HashMap::new()),
+            error_ttl: u64::try_from(self.error_ttl.as_millis()).unwrap_or(u64::MAX),
+            errors: Mutex::new(HashMap::new()),
+        });
+
+        (dual_cache, rx)
+    }
+
+    // Helper: The empty main cache both `build_with_hasher` and `build_local` start from
+    fn new_cache<K, V, S>(&self, hasher: S) -> Cache<K, V, S>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+        S: BuildHasher + Clone,
+    {
+        let sketch = (self.admission_filter || self.frequency_sketch)
+            .then(|| Arc::new(Mutex::new(FrequencySketch::new(self.capacity))));
+        Cache {
+            arena: SegmentedVec::new(),
+            index: ShardedIndex::with_capacity_and_hasher(self.capacity, hasher),
+            counter_sum: 0,
+            evict_point: self.evict_point.unwrap_or(self.capacity).min(self.capacity),
+            base_evict_point: self.evict_point.unwrap_or(self.capacity).min(self.capacity),
+            capacity: self.capacity,
+            step_ratio: self.step_ratio,
+            insertions: 0,
+            evictions: 0,
+            expirations: 0,
+            has_idle_entries: false,
+            has_priorities: false,
+            pending_writes: 0,
+            last_commit: Instant::now(),
+            dirty: false,
+            listener: None,
+            events: None,
+            watches: None,
+            invalidation: None,
+            #[cfg(feature = "disk")]
+            oplog: None,
+            weigher: None,
+            max_weight: u64::MAX,
+            total_weight: 0,
+            membrane: Arc::new(Mutex::new(Box::new(ViscousMembrane::new()))),
+            decay: self.decay,
+            climb: self.climb,
+            decay_interval: self.decay_interval,
+            last_decay: Instant::now(),
+            garbage: 0,
+            free: Vec::new(),
+            clock: Arc::clone(&self.clock),
+            refresher: None,
+            refresh_ahead: None,
+            generation: 0,
+            pinned_count: 0,
+            promotion_hits: self.promotion_hits,
+            admission: sketch.clone().filter(|_| self.admission_filter),
+            rejections: 0,
+            frequency: sketch.filter(|_| self.frequency_sketch),
+            sketch_resets: 0,
+            expiry: self.expiry_wheel.then(|| Arc::new(Mutex::new(ExpiryWheel::new()))),
+            watermarks: self.watermarks,
+            dropper: None,
+            memory_alarm: None,
+            bans: None,
+            ttl_jitter: self.ttl_jitter,
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -49,90 +828,843 @@ where
     V: Clone + Send + Sync + 'static,
 {
     /// A. Initialization
-    pub fn new(capacity: usize) -> (Arc<Self>, Receiver<K>) {
-        // Create bounded channel (e.g., 10,000 as suggested context)
-        let (tx, rx) = bounded(10_000);
+    pub fn new(capacity: usize) -> (Arc<Self>, Receiver<Signal<K>>) {
+        Self::builder(capacity).build()
+    }
 
-        let initial_cache = Cache {
-            arena: Vec::with_capacity(capacity),
-            index: HashMap::with_capacity(capacity),
-            counter_sum: 0,
-            evict_point: capacity, // Initialized to capacity per spec
-            capacity,
+    /// Starts a `DualCacheBuilder` for non-default channel/membrane settings.
+    pub fn builder(capacity: usize) -> DualCacheBuilder {
+        DualCacheBuilder::new(capacity)
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Default-configured cache hashing keys with `hasher`.
+    pub fn with_hasher(capacity: usize, hasher: S) -> (Arc<Self>, Receiver<Signal<K>>) {
+        DualCacheBuilder::new(capacity).build_with_hasher(hasher)
+    }
+
+    /// B. The Read Path (Lock-Free & Lossy)
+    /// Accepts any borrowed form of the key (e.g. `&str` for `String` keys).
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 4. Return value clone
+        self.read(key, |node| node.value.clone())
+    }
+
+    /// Reads the value by reference, avoiding the clone done by `get`.
+    /// `read` runs against the snapshot; keep it short.
+    pub fn get_with<Q, R>(&self, key: &Q, read: impl FnOnce(&V) -> R) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.read(key, |node| read(&node.value))
+    }
+
+    /// Like `get`, but sends no promotion signal and leaves the hit/miss
+    /// counters untouched.
+    pub fn peek<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.probe(&self.mirror.load(), key) {
+            Probe::Hit(node) | Probe::Stale(node) => Some(node.value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether `key` is live (and unexpired) in the mirror. Sends no signal.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        matches!(self.probe(&self.mirror.load(), key), Probe::Hit(_) | Probe::Stale(_))
+    }
+
+    /// Prefetch hint: sends the same lossy promotion signal as a `get` hit,
+    /// without cloning the value or touching the hit/miss counters.
+    pub fn hint<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Probe::Hit(node) | Probe::Stale(node) = self.probe(&self.mirror.load(), key) {
+            self.signal_hit(&node.key);
+        }
+    }
+
+    /// Slow path: reads the authoritative state under the main lock, so
+    /// writes are visible before they are committed. Contends with the
+    /// writer and daemon; like `peek`, sends no signal and counts no hit.
+    pub fn get_fresh<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let guard = self.main.lock();
+        let idx = guard.position(key)?;
+        let node = &guard.arena[idx];
+        (!node.is_expired(guard.current_time())).then(|| node.value.clone())
+    }
+
+    /// Approximate entry count of the mirror: arena slots minus unlinked
+    /// garbage. Expired entries no reader or climb has noticed still count.
+    pub fn len(&self) -> usize {
+        self.mirror.load().live_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.mirror.load().capacity
+    }
+
+    /// Mirrors published by every commit (see `DualCacheBuilder::mirror_replicas`).
+    pub fn mirror_replicas(&self) -> usize {
+        self.replicas.len() + 1
+    }
+
+    /// `len / capacity` of the mirror (0.0 for a zero-capacity cache).
+    pub fn occupancy_ratio(&self) -> f64 {
+        let snapshot = self.mirror.load();
+        if snapshot.capacity == 0 {
+            return 0.0;
+        }
+        snapshot.live_len() as f64 / snapshot.capacity as f64
+    }
+
+    // Helper: Shared read path (validation, TTL check, signaling, stats)
+    fn read<Q, R>(&self, key: &Q, project: impl FnOnce(&Node<K, V>) -> R) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 1. Snapshot Access
+        self.read_in(&self.mirror.load(), key, project)
+    }
+
+    // Helper: Mirror replica `replica` (0 is the primary `mirror`), wrapping around
+    fn replica(&self, replica: usize) -> &Mirror<K, V, S> {
+        match replica % (self.replicas.len() + 1) {
+            0 => &self.mirror,
+            i => &self.replicas[i - 1],
+        }
+    }
+
+    // Helper: The read path against an already-loaded snapshot
+    fn read_in<Q, R>(
+        &self,
+        snapshot: &Cache<K, V, S>,
+        key: &Q,
+        project: impl FnOnce(&Node<K, V>) -> R,
+    ) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.probe(snapshot, key) {
+            Probe::Hit(node) => {
+                // 3. Lossy Signaling (signals carry an owned key, taken from the node)
+                self.signal_hit(&node.key);
+                self.counters.hits.incr();
+                Some(project(node))
+            }
+            Probe::Stale(node) => {
+                self.signal(Signal::Refresh(node.key.clone()));
+                self.counters.hits.incr();
+                Some(project(node))
+            }
+            Probe::Expired(node) => {
+                if self.expiry_signals {
+                    self.signal(Signal::Expired(node.key.clone()));
+                }
+                self.counters.misses.incr();
+                None
+            }
+            Probe::Miss => {
+                self.counters.misses.incr();
+                None
+            }
+        }
+    }
+
+    // Helper: Validates one key against a snapshot (no signaling, no stats)
+    fn probe<'a, Q>(&self, snapshot: &'a Cache<K, V, S>, key: &Q) -> Probe<'a, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        // 2. Lazy Validation
+        if let Some(&idx) = snapshot.index.get(key) {
+            // CRITICAL CHECK: Verify index bounds and key identity
+            // Handles cases where index map points to truncated/reused slots
+            if idx < snapshot.arena.len() && snapshot.arena[idx].key.borrow() == key {
+                let node = &snapshot.arena[idx];
+                if !self.read_ttl_check {
+                    return Probe::Hit(node);
+                }
+
+                // Expiry is otherwise only evaluated by the Daemon's climb,
+                // so the snapshot may still hold nodes past their deadline.
+                let now = snapshot.current_time();
+                if node.is_expired(now) {
+                    return Probe::Expired(node);
+                }
+                if node.is_stale(now) {
+                    return Probe::Stale(node);
+                }
+                return Probe::Hit(node);
+            }
+        }
+        Probe::Miss
+    }
+
+    /// Looks up many keys against a single mirror snapshot. All hits are
+    /// reported to the Daemon as one `Signal::ReadBatch` message.
+    pub fn get_many<I>(&self, keys: I) -> Vec<Option<V>>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut results = Vec::new();
+        self.read_many(keys, |_, value| results.push(value));
+        results
+    }
+
+    /// Like `get_many`, returning only the hits keyed by their key.
+    pub fn get_many_map<I>(&self, keys: I) -> HashMap<K, V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut results = HashMap::new();
+        self.read_many(keys, |key, value| {
+            if let Some(value) = value {
+                results.insert(key, value);
+            }
+        });
+        results
+    }
+
+    fn read_many<I>(&self, keys: I, sink: impl FnMut(K, Option<V>))
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.read_many_in(&self.mirror.load(), keys, sink);
+    }
+
+    // Helper: `read_many` against an already-loaded snapshot
+    fn read_many_in<I>(&self, cache_guard: &Cache<K, V, S>, keys: I, mut sink: impl FnMut(K, Option<V>))
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut hits = Vec::new();
+
+        for key in keys {
+            let value = match self.probe(cache_guard, &key) {
+                Probe::Hit(node) => {
+                    self.counters.hits.incr();
+                    if self.coalescer.is_some() {
+                        self.signal_hit(&node.key);
+                    } else if self.sample_hit() {
+                        hits.push(node.key.clone());
+                    }
+                    Some(node.value.clone())
+                }
+                Probe::Stale(node) => {
+                    self.counters.hits.incr();
+                    self.signal(Signal::Refresh(node.key.clone()));
+                    Some(node.value.clone())
+                }
+                Probe::Expired(node) => {
+                    if self.expiry_signals {
+                        self.signal(Signal::Expired(node.key.clone()));
+                    }
+                    self.counters.misses.incr();
+                    None
+                }
+                Probe::Miss => {
+                    self.counters.misses.incr();
+                    None
+                }
+            };
+            sink(key, value);
+        }
+
+        if !hits.is_empty() {
+            self.signal(Signal::ReadBatch(hits));
+        }
+    }
+
+    // Helper: Reports a hit, through the coalescer when enabled
+    fn signal_hit(&self, key: &K) {
+        if !self.sample_hit() {
+            return;
+        }
+        match &self.coalescer {
+            Some(coalescer) => {
+                if let Some(counts) = coalescer.record(key) {
+                    self.signal(Signal::ReadCounts(counts));
+                }
+            }
+            None => self.signal(Signal::Read(key.clone())),
+        }
+    }
+
+    // Helper: Whether this hit is reported under `SignalPolicy::Sample`
+    fn sample_hit(&self) -> bool {
+        match self.signal_policy {
+            SignalPolicy::Sample(one_in) => stats::sample(one_in),
+            _ => true,
+        }
+    }
+
+    // Helper: Sends a signal, applying the SignalPolicy when the channel is full
+    fn signal(&self, signal: Signal<K>) {
+        if self.signal_policy == SignalPolicy::Inline {
+            // Waiting could deadlock a read made inside a write on this thread
+            if self.main.is_locked() {
+                self.counters.dropped_signals.incr();
+            } else {
+                self.process_signal(signal);
+            }
+            return;
+        }
+
+        let signal = match self.lazy_tx.try_send(signal) {
+            Ok(()) => return,
+            Err(TrySendError::Full(signal)) => signal,
+            // No Daemon is listening; nothing to preserve
+            Err(TrySendError::Disconnected(_)) => {
+                self.counters.dropped_signals.incr();
+                return;
+            }
         };
 
-        let dual_cache = Arc::new(Self {
-            main: Mutex::new(initial_cache.clone()),
-            mirror: ArcSwap::from_pointee(initial_cache),
-            lazy_tx: tx,
+        let delivered = match self.signal_policy {
+            SignalPolicy::DropNewest | SignalPolicy::Sample(_) => false,
+            SignalPolicy::DropOldest => {
+                // Make room by discarding the head of the queue; the Daemon may
+                // have drained it meanwhile, in which case nothing is lost.
+                if let Some(rx) = &self.lazy_rx
+                    && rx.try_recv().is_ok()
+                {
+                    self.counters.dropped_signals.incr();
+                }
+                self.lazy_tx.try_send(signal).is_ok()
+            }
+            SignalPolicy::Block(timeout) => self.lazy_tx.send_timeout(signal, timeout).is_ok(),
+            SignalPolicy::Inline => unreachable!("inline signals never reach the channel"),
+        };
+        if !delivered {
+            self.counters.dropped_signals.incr();
+        }
+    }
+
+    /// Internal helper to sync Main state to Mirror
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn sync_mirror(&self) -> u64 {
+        let mut main_lock = self.main.lock();
+        if !main_lock.dirty {
+            // Nothing changed: the mirror already shows this generation
+            return main_lock.generation;
+        }
+        self.publish(&mut main_lock)
+    }
+
+    // Helper: Publishes an already-locked main state, returning its generation
+    fn publish(&self, main: &mut Cache<K, V, S>) -> u64 {
+        let started = Instant::now();
+        main.pending_writes = 0;
+        main.dirty = false;
+        main.last_commit = started;
+        main.generation += 1;
+        // Structural clone: O(segments), node data stays shared until the
+        // writer next touches a segment (copy-on-write).
+        let snapshot = Arc::new(main.clone());
+        // Update ArcSwap (replicas first; `published` follows the primary)
+        // Replaced snapshots may hold the last reference to truncated
+        // segments; the dropper frees them off the lock when deferred drop is on
+        for replica in self.replicas.iter() {
+            main.discard(Garbage::Snapshot(replica.swap(Arc::clone(&snapshot))));
+        }
+        main.discard(Garbage::Snapshot(self.mirror.swap(snapshot)));
+        self.published.store(main.generation, Ordering::Release);
+        #[cfg(feature = "metrics")]
+        metrics::record_commit(started.elapsed());
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            generation = main.generation,
+            arena_len = main.arena.len(),
+            commit_us = started.elapsed().as_micros() as u64,
+            "mirror published"
+        );
+
+        // Wake visibility waiters (main is still locked, so generations arrive in order)
+        if let Some(watches) = &main.watches {
+            watches.publish(&self.mirror.load());
+        }
+        main.emit(|| CacheEvent::Committed(main.generation));
+        *self.committed.lock() = main.generation;
+        self.commit_cv.notify_all();
+        #[cfg(feature = "async")]
+        {
+            self.commit_notify.notify_waiters();
+            self.commit_watch.send_replace(main.generation);
+        }
+        main.generation
+    }
+
+    // Helper: Runs a user write under the main lock and applies the CommitPolicy
+    fn write<R>(&self, op: impl FnOnce(&mut Cache<K, V, S>) -> R) -> R {
+        let mut guard = self.main.lock();
+        let result = op(&mut guard);
+        self.after_write(&mut guard);
+        result
+    }
+
+    // Helper: Counts a write against an already-locked main and commits if due
+    fn after_write(&self, main: &mut Cache<K, V, S>) {
+        self.after_writes(main, 1);
+    }
+
+    fn after_writes(&self, main: &mut Cache<K, V, S>, writes: u64) {
+        main.pending_writes += writes;
+        main.dirty = true;
+
+        let due = match self.commit_policy {
+            CommitPolicy::Manual => false,
+            CommitPolicy::EveryNWrites(n) => main.pending_writes >= n,
+            CommitPolicy::Interval(interval) => main.last_commit.elapsed() >= interval,
+        };
+        if due {
+            self.publish(main);
+        }
+    }
+    
+    // Public wrappers for Write/Daemon operations (to be called by the Daemon thread)
+    // In a real system, these would likely be called by a worker processing `rx`.
+    
+    pub fn process_read_signal(&self, key: K) {
+        let mut guard = self.main.lock();
+        guard.viscous_climb(key);
+    }
+
+    /// Write-path counterpart of `hint`: climbs `key` one step in `main`
+    /// right away (no signal, no value clone). Visible after the next commit.
+    /// Returns whether the key was live.
+    pub fn touch<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let mut guard = self.main.lock();
+        let Some(idx) = guard.position(key) else { return false };
+        let key = guard.arena[idx].key.clone();
+        guard.viscous_climb(key);
+        true
+    }
+
+    /// Applies one message from the signal channel.
+    pub fn process_signal(&self, signal: Signal<K>) {
+        match signal {
+            Signal::Read(key) => self.process_read_signal(key),
+            Signal::Expired(key) => {
+                let mut guard = self.main.lock();
+                guard.expire_if_stale(&key);
+            }
+            Signal::ReadBatch(keys) => {
+                let mut guard = self.main.lock();
+                for key in keys {
+                    guard.viscous_climb(key);
+                }
+            }
+            Signal::ReadCounts(counts) => self.apply_read_counts(counts),
+            Signal::Refresh(key) => self.refresh(key),
+        }
+    }
+
+    /// Applies a batch of channel messages. Read signals (`Read`, `ReadBatch`,
+    /// `ReadCounts`) are merged per key and applied under a single lock; the
+    /// others are handled as by `process_signal`.
+    pub fn process_signals(&self, signals: impl IntoIterator<Item = Signal<K>>) {
+        let mut hits: HashMap<K, u64> = HashMap::new();
+        for signal in signals {
+            match signal {
+                Signal::Read(key) => *hits.entry(key).or_default() += 1,
+                Signal::ReadBatch(keys) => {
+                    for (key, count) in count_reads(keys) {
+                        *hits.entry(key).or_default() += count;
+                    }
+                }
+                Signal::ReadCounts(counts) => {
+                    for (key, count) in counts {
+                        *hits.entry(key).or_default() += count;
+                    }
+                }
+                signal => self.process_signal(signal),
+            }
+        }
+        if !hits.is_empty() {
+            Self::climb_batch(&mut self.main.lock(), hits);
+        }
+    }
+
+    /// Applies a batch of read signals under a single lock. Repeated keys are
+    /// merged into one climb worth their hit count. Returns the distinct keys.
+    pub fn process_read_signals(&self, keys: impl IntoIterator<Item = K>) -> usize {
+        let hits = count_reads(keys);
+        if hits.is_empty() {
+            return 0;
+        }
+        Self::climb_batch(&mut self.main.lock(), hits)
+    }
+
+    /// `process_read_signals`, then the `maintenance` steps, all under one lock.
+    pub fn process_read_signals_and_maintain(&self, keys: impl IntoIterator<Item = K>) -> usize {
+        let hits = count_reads(keys);
+        let mut guard = self.main.lock();
+        let distinct = Self::climb_batch(&mut guard, hits);
+        self.maintain(&mut guard);
+        distinct
+    }
+
+    // Helper: Climbs every key by its merged hit count; returns the key count
+    fn climb_batch(cache: &mut Cache<K, V, S>, hits: HashMap<K, u64>) -> usize {
+        let distinct = hits.len();
+        for (key, hits) in hits {
+            cache.viscous_climb_by(key, hits);
+        }
+        distinct
+    }
+
+    /// Applies hits still buffered by read coalescing (no-op when disabled).
+    pub fn flush_read_signals(&self) {
+        if let Some(coalescer) = &self.coalescer {
+            self.apply_read_counts(coalescer.drain());
+        }
+    }
+
+    fn apply_read_counts(&self, counts: Vec<(K, u64)>) {
+        if counts.is_empty() {
+            return;
+        }
+        let mut guard = self.main.lock();
+        for (key, hits) in counts {
+            guard.viscous_climb_by(key, hits);
+        }
+    }
+
+    pub fn insert(&self, key: K, value: V, ttl_secs: u64) {
+        self.write(|cache| cache.gatsby_insert(key, value, ttl_secs));
+    }
+
+    /// Inserts with both an absolute TTL and a time-to-idle window: the entry
+    /// also expires if no read signal reaches it within `tti_secs`.
+    /// Like `insert`, an existing key only has its value replaced.
+    pub fn insert_with_tti(&self, key: K, value: V, ttl_secs: u64, tti_secs: u64) {
+        self.write(|cache| {
+            let mut node = cache.new_node(key, value, ttl_secs);
+            node.tti = secs_to_millis(tti_secs);
+            cache.gatsby_insert_node(node);
+        });
+    }
+
+    /// Returns the cached value, computing and inserting it if missing.
+    /// The mirror is checked first; on a miss `init` runs under the main lock,
+    /// so concurrent callers for the same key compute the value only once.
+    /// The inserted value becomes visible to `get` after the next `commit`.
+    pub fn get_or_insert_with<F>(&self, key: K, ttl_secs: u64, init: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+
+        self.write(|cache| {
+            // Re-check under the lock: another writer may have inserted meanwhile
+            if let Some(idx) = cache.unexpired_position(&key) {
+                return cache.arena[idx].value.clone();
+            }
+            let value = init();
+            cache.gatsby_insert(key, value.clone(), ttl_secs);
+            value
+        })
+    }
+
+    /// Inserts with the default TTL configured on the builder.
+    pub fn put(&self, key: K, value: V) {
+        self.insert(key, value, self.default_ttl);
+    }
+
+    pub fn delete<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            cache.double_swap_delete(key);
         });
+    }
+
+    /// Keeps only the live entries for which `keep` returns true, then commits.
+    /// Returns the number of entries removed.
+    pub fn retain(&self, mut keep: impl FnMut(&K, &V) -> bool) -> usize {
+        self.invalidate_where(|key, value| !keep(key, value))
+    }
+
+    /// Deletes every live entry matching `predicate` (e.g. all keys of one
+    /// tenant) under a single lock, then commits once.
+    /// Returns the number of entries removed.
+    pub fn invalidate_where(&self, mut predicate: impl FnMut(&K, &V) -> bool) -> usize {
+        let mut guard = self.main.lock();
+        let removed = guard.delete_where(|node| predicate(&node.key, &node.value));
+        self.publish(&mut guard);
+        removed
+    }
+
+    pub fn maintenance(&self) {
+        self.maintain(&mut self.main.lock());
+        self.purge_errors();
+        self.refresh_ahead();
+    }
+
+    // Helper: The maintenance steps, on an already locked `main`
+    fn maintain(&self, cache: &mut Cache<K, V, S>) {
+        cache.expire_due();
+        cache.purge_idle();
+        cache.purge_bans();
+        cache.sweep_garbage();
+        cache.shed_to_low_watermark();
+        cache.purge_stale_index();
+        cache.decay_counters();
+        cache.update_evict_point(self.counters.hits.get(), self.counters.misses.get());
+        cache.check_memory_alarm();
+    }
+    
+    pub fn update<Q>(&self, key: &Q, value: V)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| cache.update_value(key, value));
+    }
+    
+    /// Must be called manually or periodically to refresh the read-view.
+    /// Returns the generation now visible to readers (see `current_generation`).
+    /// Skips the clone and keeps the current generation when nothing changed
+    /// since the last commit (see `is_dirty`).
+    pub fn commit(&self) -> u64 {
+        self.sync_mirror()
+    }
 
-        (dual_cache, rx)
+    /// Whether `main` holds changes (writes, climbs, expirations, membrane
+    /// moves) that readers will not see until the next commit.
+    pub fn is_dirty(&self) -> bool {
+        self.main.lock().dirty
     }
 
-    /// B. The Read Path (Lock-Free & Lossy)
-    pub fn get(&self, key: &K) -> Option<V> {
-        // 1. Snapshot Access
-        let cache_guard = self.mirror.load();
-        
-        // 2. Lazy Validation
-        if let Some(&idx) = cache_guard.index.get(key) {
-            // CRITICAL CHECK: Verify index bounds and key identity
-            // Handles cases where index map points to truncated/reused slots
-            if idx < cache_guard.arena.len() && &cache_guard.arena[idx].key == key {
-                
-                // 3. Lossy Signaling
-                // Ignore error if full (Drop signal)
-                let _ = self.lazy_tx.try_send(key.clone());
-
-                // 4. Return value clone
-                return Some(cache_guard.arena[idx].value.clone());
+    /// Generation of the mirror readers currently see. Starts at 0 and
+    /// increases by one per commit.
+    pub fn current_generation(&self) -> u64 {
+        self.mirror.load().generation
+    }
+
+    /// Blocks until a commit at or after `generation` is visible to readers.
+    /// Pair with the value returned by `commit` for read-your-writes.
+    pub fn wait_for_generation(&self, generation: u64) {
+        let mut committed = self.committed.lock();
+        while *committed < generation {
+            self.commit_cv.wait(&mut committed);
+        }
+    }
+
+    /// Like `wait_for_generation`, giving up after `timeout`.
+    /// Returns whether the generation became visible.
+    pub fn wait_for_generation_timeout(&self, generation: u64, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut committed = self.committed.lock();
+        while *committed < generation {
+            if self.commit_cv.wait_until(&mut committed, deadline).timed_out() {
+                return *committed >= generation;
             }
         }
+        true
+    }
 
-        None
+    /// Physically removes expired-but-present (unlinked) nodes, compacting the
+    /// arena in rank order. Returns the number of slots reclaimed.
+    /// Also run by `maintenance` whenever expirations left garbage behind.
+    pub fn sweep(&self) -> usize {
+        let mut guard = self.main.lock();
+        guard.garbage = guard.garbage.max(1); // Force the scan
+        guard.sweep_garbage()
     }
 
-    /// Internal helper to sync Main state to Mirror
-    fn sync_mirror(&self) {
-        let main_lock = self.main.lock();
-        // Deep Clone of the current main state
-        let snapshot = main_lock.clone();
-        // Update ArcSwap
-        self.mirror.store(Arc::new(snapshot));
+    /// Removes index entries left dangling by cliff-edge truncation (their
+    /// slot is gone or now holds another key). Returns the number removed.
+    /// Also run by `maintenance`; lookups stay correct without it.
+    pub fn purge_index(&self) -> usize {
+        self.main.lock().purge_stale_index()
     }
-    
-    // Public wrappers for Write/Daemon operations (to be called by the Daemon thread)
-    // In a real system, these would likely be called by a worker processing `rx`.
-    
-    pub fn process_read_signal(&self, key: K) {
-        let mut guard = self.main.lock();
-        guard.viscous_climb(key);
+
+    /// Registers hooks for evicted, expired, and deleted nodes (replacing any previous one).
+    pub fn set_listener(&self, listener: Arc<dyn CacheListener<K, V>>) {
+        self.main.lock().listener = Some(listener);
     }
 
-    pub fn insert(&self, key: K, value: V, ttl_secs: u64) {
+    /// Switches capacity accounting to weights: inserts evict until the total
+    /// weight of live nodes fits `max_weight`, and the membrane never protects
+    /// more than `max_weight`. The entry-count capacity still applies.
+    pub fn set_weigher(&self, max_weight: u64, weigher: Weigher<K, V>) {
         let mut guard = self.main.lock();
-        guard.gatsby_insert(key, value, ttl_secs);
+        guard.weigher = Some(weigher);
+        guard.max_weight = max_weight;
+        guard.total_weight = (0..guard.arena.len())
+            .filter(|&i| guard.position(&guard.arena[i].key) == Some(i))
+            .map(|i| guard.weigh(&guard.arena[i]))
+            .sum();
     }
 
-    pub fn delete(&self, key: &K) {
-        let mut guard = self.main.lock();
-        guard.double_swap_delete(key);
+    /// Replaces the membrane dynamics used by `maintenance` (default: `ViscousMembrane`).
+    pub fn set_evict_point_policy(&self, policy: impl EvictPointPolicy + 'static) {
+        self.main.lock().membrane = Arc::new(Mutex::new(Box::new(policy)));
     }
 
-    pub fn maintenance(&self) {
+    /// Changes the capacity at runtime and commits. `evict_point` is clamped
+    /// to the new capacity; when shrinking below the current length the
+    /// coldest tail is evicted (cliff edge at `new_capacity`).
+    pub fn resize(&self, new_capacity: usize) {
         let mut guard = self.main.lock();
-        guard.update_evict_point();
+        guard.resize(new_capacity);
+        self.publish(&mut guard);
     }
-    
-    pub fn update(&self, key: &K, value: V) {
-        let mut guard = self.main.lock();
-        guard.update_value(key, value);
+
+    /// Snapshot of hit/miss, write-path, and occupancy counters.
+    /// Read signals lost to a full channel so far (see `SignalPolicy`).
+    /// Lock-free, unlike `stats`.
+    pub fn dropped_signals(&self) -> u64 {
+        self.counters.dropped_signals.get()
     }
-    
-    /// Must be called manually or periodically to refresh the read-view
-    pub fn commit(&self) {
-        self.sync_mirror();
+
+    /// Read signals currently queued for the Daemon.
+    pub fn channel_len(&self) -> usize {
+        self.lazy_tx.len()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let guard = self.main.lock();
+        CacheStats {
+            hits: self.counters.hits.get(),
+            misses: self.counters.misses.get(),
+            insertions: guard.insertions,
+            evictions: guard.evictions,
+            expirations: guard.expirations,
+            rejections: guard.rejections,
+            dropped_signals: self.counters.dropped_signals.get(),
+            channel_len: self.lazy_tx.len(),
+            channel_capacity: self.lazy_tx.capacity().unwrap_or(0),
+            occupancy: guard.arena.len(),
+            capacity: guard.capacity,
+            total_weight: guard.total_weight,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// 2.1 Shared Values (Arc<V>)
+// -----------------------------------------------------------------------------
+
+/// A cache whose values live behind `Arc`: `get` and the commit-time segment
+/// copies only bump a refcount, however large the value is.
+pub type SharedDualCache<K, T> = DualCache<K, Arc<T>>;
+
+impl<K, T> DualCache<K, Arc<T>>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    /// Same as `get`; named for call sites that want the refcount semantics explicit.
+    pub fn get_arc<Q>(&self, key: &Q) -> Option<Arc<T>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key)
+    }
+
+    /// Wraps `value` in an `Arc` and inserts it.
+    pub fn insert_arc(&self, key: K, value: T, ttl_secs: u64) -> Arc<T> {
+        let value = Arc::new(value);
+        self.insert(key, Arc::clone(&value), ttl_secs);
+        value
+    }
+}
+
+// -----------------------------------------------------------------------------
+// 2.2 Negative Caching
+// -----------------------------------------------------------------------------
+
+/// A cached lookup result: either a value or a remembered absence
+/// ("not found" / failed lookup), usually stored with a short TTL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CachedValue<V> {
+    Present(V),
+    Negative,
+}
+
+/// Result of `NegativeDualCache::lookup`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lookup<V> {
+    /// A cached value.
+    Hit(V),
+    /// The key is cached as absent; skip the backing store.
+    NegativeHit,
+    /// Nothing cached; the caller must consult the backing store.
+    Miss,
+}
+
+/// A cache that can also remember misses.
+pub type NegativeDualCache<K, V> = DualCache<K, CachedValue<V>>;
+
+impl<K, T> DualCache<K, CachedValue<T>>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    /// `get`, distinguishing positive hits, negative hits, and misses.
+    pub fn lookup<Q>(&self, key: &Q) -> Lookup<T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.get(key) {
+            Some(CachedValue::Present(value)) => Lookup::Hit(value),
+            Some(CachedValue::Negative) => Lookup::NegativeHit,
+            None => Lookup::Miss,
+        }
+    }
+
+    pub fn insert_present(&self, key: K, value: T, ttl_secs: u64) {
+        self.insert(key, CachedValue::Present(value), ttl_secs);
+    }
+
+    /// Remembers that `key` has no value for `ttl_secs`.
+    pub fn insert_negative(&self, key: K, ttl_secs: u64) {
+        self.insert(key, CachedValue::Negative, ttl_secs);
     }
 }
 
@@ -140,16 +1672,47 @@ where
 // 3. Internal Cache Logic (The Write Path)
 // -----------------------------------------------------------------------------
 
-impl<K, V> Cache<K, V>
+impl<K, V, S> Cache<K, V, S>
 where
     K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
 {
-    // Helper: Gets current time as u64
-    fn current_time() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs()
+    // Helper: Gets current clock time in milliseconds
+    fn current_time(&self) -> u64 {
+        self.clock.now_millis()
+    }
+
+    // Helper: Arena slots minus known garbage
+    fn live_len(&self) -> usize {
+        self.arena.len().saturating_sub(self.garbage)
+    }
+
+    // Helper: Resolves a key to its arena slot, applying Lazy Validation
+    fn position<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.index.get(key) {
+            Some(&i) if i < self.arena.len() && self.arena[i].key.borrow() == key => Some(i),
+            _ => None,
+        }
+    }
+
+    // Helper: `position`, but an expired node counts as absent and is
+    // invalidated on the spot (the mirror may not have purged it yet)
+    fn unexpired_position<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.position(key)?;
+        if self.arena[idx].is_expired(self.current_time()) {
+            self.invalidate_expired(idx);
+            return None;
+        }
+        Some(idx)
     }
 
     // Helper: Swaps two nodes and updates the index map
@@ -158,84 +1721,334 @@ where
             return;
         }
 
+        // Garbage (unlinked) nodes must stay unlinked: re-indexing them would
+        // resurrect an expired key or hijack a re-inserted one.
+        let live_a = self.is_live(idx_a);
+        let live_b = self.is_live(idx_b);
+
         self.arena.swap(idx_a, idx_b);
 
         // Update indices for the swapped keys
-        let key_a = self.arena[idx_a].key.clone();
-        let key_b = self.arena[idx_b].key.clone();
+        if live_b {
+            let key = self.arena[idx_a].key.clone();
+            self.index.insert(key, idx_a);
+        }
+        if live_a {
+            let key = self.arena[idx_b].key.clone();
+            self.index.insert(key, idx_b);
+        }
+    }
 
-        self.index.insert(key_a, idx_a);
-        self.index.insert(key_b, idx_b);
+    // Helper: Whether the slot holds the node its key is indexed to
+    fn is_live(&self, idx: usize) -> bool {
+        self.index.get(&self.arena[idx].key) == Some(&idx)
     }
 
     /// C.1. Viscous Climb
     fn viscous_climb(&mut self, key: K) {
+        self.viscous_climb_by(key, 1);
+    }
+
+    // Helper: Applies `hits` coalesced reads; each hit is worth one step
+    fn viscous_climb_by(&mut self, key: K, hits: u64) {
         // Find the key
         let current_index = match self.index.get(&key) {
             Some(&i) if i < self.arena.len() && self.arena[i].key == key => i,
             _ => return, // Key not found or invalid
         };
 
-        // Increment counter
-        self.arena[current_index].counter = self.arena[current_index].counter.saturating_add(1);
-        self.counter_sum = self.counter_sum.saturating_add(1);
+        let mut gained = hits;
+        if let Some(sketch) = self.admission.as_ref().or(self.frequency.as_ref()) {
+            let mut sketch = sketch.lock();
+            let before = sketch.estimate(&key);
+            sketch.increment(&key, hits);
+            // In sketch mode the total follows the (saturating) estimate
+            if self.frequency.is_some() {
+                gained = u64::from(sketch.estimate(&key).saturating_sub(before));
+            }
+        }
+
+        self.dirty = true;
+
+        // Increment counter (the sketch already counted the hits in sketch mode)
+        if self.frequency.is_none() {
+            self.arena[current_index].counter = self.arena[current_index].counter.saturating_add(hits);
+        }
+        self.counter_sum = self.counter_sum.saturating_add(gained);
 
         // Expiration Check
-        let now = Self::current_time();
-        if now > self.arena[current_index].time_stamp {
-            // Swap expired node with evict_point + 1
-            let target = self.evict_point + 1;
-            
-            // Safety check: ensure target is within bounds. 
-            // If arena is small, we just remove it without the specific swap logic to avoid panic.
-            if target < self.arena.len() {
-                self.swap_nodes(current_index, target);
-            }
-            
-            // Remove from index (effectively validating the expiration)
-            // Note: The node remains in arena (garbage) until overwritten or truncated
-            self.index.remove(&key);
+        let now = self.current_time();
+        if self.arena[current_index].is_expired(now) {
+            self.invalidate_expired(current_index);
             return;
         }
+        // The access refreshes the idle deadline
+        self.arena[current_index].last_access = now;
 
         // Physics: Swap with current_index - 1 (Move towards 0)
-        if current_index > 0 {
-            self.swap_nodes(current_index, current_index - 1);
+        let mut steps = self.climb_steps(current_index, hits).min(current_index);
+
+        // Segmented mode: a probation node stops at the membrane until it has
+        // earned `promotion_hits` hits (its counter starts at 1)
+        if self.promotion_hits > 0
+            && current_index >= self.evict_point
+            && self.frequency(current_index) <= self.promotion_hits
+        {
+            steps = steps.min(current_index - self.evict_point);
+        }
+        for idx in (current_index - steps + 1..=current_index).rev() {
+            self.swap_nodes(idx, idx - 1);
+        }
+    }
+
+    // Helper: Positions the node at `idx` climbs for `hits` applied hits
+    fn climb_steps(&self, idx: usize, hits: u64) -> usize {
+        let hits = usize::try_from(hits).unwrap_or(usize::MAX);
+        match self.climb {
+            ClimbDistance::Fixed(n) => hits.saturating_mul(n),
+            ClimbDistance::Logarithmic => hits.saturating_mul(idx.checked_ilog2().unwrap_or(0) as usize + 1),
+            ClimbDistance::Proportional(ratio) => {
+                let above = idx.checked_sub(1).map_or(0, |up| self.frequency(up));
+                let lead = self.frequency(idx).saturating_sub(above);
+                ((lead as f64 * ratio) as usize).max(hits)
+            }
+        }
+    }
+
+    // Helper: Moves an expired node to the probation zone and unlinks it
+    fn invalidate_expired(&mut self, idx: usize) {
+        // Swap expired node with evict_point + 1
+        let target = self.evict_point + 1;
+
+        // Safety check: ensure target is within bounds. 
+        // If arena is small, we just remove it without the specific swap logic to avoid panic.
+        let mut slot = idx;
+        if target < self.arena.len() {
+            self.swap_nodes(idx, target);
+            slot = target;
+        }
+
+        // Remove from index (effectively validating the expiration)
+        // Note: The node remains in arena (garbage) until overwritten or truncated
+        let key = self.arena[slot].key.clone();
+        self.index.remove(&key);
+        self.expirations += 1;
+        self.garbage += 1;
+        self.free.push(slot);
+        self.dirty = true;
+        self.total_weight = self.total_weight.saturating_sub(self.weigh(&self.arena[slot]));
+
+        if let Some(listener) = &self.listener {
+            listener.on_expire(&self.arena[slot]);
+        }
+        self.emit(|| CacheEvent::Expired(key));
+    }
+
+    /// Handles `Signal::Expired`: re-checks the deadline against `main`,
+    /// since the entry may have been refreshed after the snapshot was taken.
+    fn expire_if_stale(&mut self, key: &K) {
+        if let Some(idx) = self.position(key)
+            && self.arena[idx].is_expired(self.current_time())
+        {
+            self.invalidate_expired(idx);
+        }
+    }
+
+    /// Invalidates every live node whose idle window has lapsed.
+    /// Only runs once TTI entries exist, since it scans the whole arena.
+    fn purge_idle(&mut self) {
+        if !self.has_idle_entries {
+            return;
+        }
+
+        let now = self.current_time();
+        let idle: Vec<K> = (0..self.arena.len())
+            .filter(|&i| self.arena[i].tti > 0 && self.arena[i].is_expired(now))
+            .map(|i| self.arena[i].key.clone())
+            .collect();
+
+        // Invalidation swaps nodes around, so resolve each key again
+        for key in idle {
+            if let Some(idx) = self.position(&key) {
+                self.invalidate_expired(idx);
+            }
+        }
+    }
+
+    /// Compacts the arena, dropping slots whose node is no longer indexed there.
+    /// Live nodes keep their relative order; their counters stay in `counter_sum`.
+    fn sweep_garbage(&mut self) -> usize {
+        if self.garbage == 0 {
+            return 0;
+        }
+        self.garbage = 0;
+        self.free.clear();
+        self.dirty = true;
+
+        let len = self.arena.len();
+        let mut write = 0;
+        for read in 0..len {
+            if self.is_live(read) {
+                if write != read {
+                    // Slot `write` holds garbage; bubble the live node into it
+                    self.arena.swap(write, read);
+                    let key = self.arena[write].key.clone();
+                    self.index.insert(key, write);
+                }
+                write += 1;
+            } else {
+                let counter = self.frequency(read);
+                self.counter_sum = self.counter_sum.saturating_sub(counter);
+            }
+        }
+
+        self.drop_tail(write);
+        len - write
+    }
+
+    /// Drops index entries that no longer resolve to their key's slot.
+    /// Skipped when the index cannot hold more entries than there are live nodes.
+    fn purge_stale_index(&mut self) -> usize {
+        if self.index.len() <= self.live_len() {
+            return 0;
+        }
+        let arena = &self.arena;
+        let removed = self
+            .index
+            .retain(|key, idx| idx < arena.len() && arena[idx].key == *key);
+        self.dirty |= removed > 0;
+        removed
+    }
+
+    /// Ages all counters per the configured `CounterDecay` (at most once per interval)
+    /// and rebuilds `counter_sum` from the decayed values. The sketch frequency
+    /// mode ages itself instead.
+    fn decay_counters(&mut self) {
+        if self.decay == CounterDecay::Off
+            || self.frequency.is_some()
+            || self.last_decay.elapsed() < self.decay_interval
+        {
+            return;
+        }
+        self.last_decay = Instant::now();
+        self.dirty = true;
+
+        let mut sum = 0u64;
+        for i in 0..self.arena.len() {
+            let node = &mut self.arena[i];
+            node.counter = match self.decay {
+                CounterDecay::Off => node.counter,
+                CounterDecay::Halve => node.counter / 2,
+                CounterDecay::Subtract(delta) => node.counter.saturating_sub(delta),
+            };
+            sum = sum.saturating_add(node.counter);
+        }
+        self.counter_sum = sum;
+    }
+
+    // Helper: `ttl` (milliseconds) moved by a random amount within the TTL
+    // jitter; zero and saturated TTLs stay exact
+    fn jittered(&self, ttl: u64) -> u64 {
+        let span = (ttl as f64 * self.ttl_jitter) as u64;
+        if span == 0 || ttl == u64::MAX {
+            return ttl;
+        }
+        let offset = stats::random() % span.saturating_mul(2).saturating_add(1);
+        (ttl - span.min(ttl)).saturating_add(offset)
+    }
+
+    // Helper: Builds a fresh node (counter 1) expiring `ttl_secs` (jittered) from now
+    fn new_node(&self, key: K, value: V, ttl_secs: u64) -> Node<K, V> {
+        let now = self.current_time();
+        // Start with 1 visibility
+        let deadline = now.saturating_add(self.jittered(secs_to_millis(ttl_secs)));
+        Node {
+            last_access: now,
+            inserted_at: now,
+            ..Node::new(key, value, 1, deadline)
         }
     }
 
     /// C.2. The Gatsby Insert
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(arena_len = self.arena.len(), evict_point = self.evict_point)
+        )
+    )]
     fn gatsby_insert(&mut self, key: K, value: V, ttl_secs: u64) {
-        // Eviction Trigger
-        if self.arena.len() == self.capacity {
+        let node = self.new_node(key, value, ttl_secs);
+        self.gatsby_insert_node(node);
+    }
+
+    // Gatsby placement of a prepared node
+    fn gatsby_insert_node(&mut self, mut node: Node<K, V>) {
+        if self.is_banned(&node.key) || !self.admit(&node) {
+            self.rejections += 1;
+            return;
+        }
+        if let Some(sketch) = &self.frequency {
+            // The sketch carries the initial visibility (`admit` counted it already)
+            if self.admission.is_none() {
+                sketch.lock().increment(&node.key, 1);
+            }
+            node.counter = 0;
+        }
+
+        // Eviction Trigger (a reusable dead slot makes room without one)
+        if self.arena.len() >= self.capacity && !self.has_free_slot() {
             // Cliff-Edge Eviction: Truncate to evict_point
             // NOTE: Do not clean up index map here (Lazy Validation handles it)
             if self.evict_point < self.arena.len() {
-                self.arena.truncate(self.evict_point);
+                self.cliff_edge_truncate();
+            } else if self.capacity > 0 {
+                // Membrane at (or past) the edge: drop the tail slot so the arena never overflows
+                self.truncate_at(self.capacity - 1);
             }
         }
+        if self.weigher.is_some() {
+            let incoming = self.weigh(&node);
+            self.evict_for_weight(incoming);
+        }
 
         // Check if key already exists to avoid duplicates (standard cache behavior),
         // though spec focuses on "Placement". Assuming new key or overwrite via update.
-        if self.index.contains_key(&key) {
-            self.update_value(&key, value);
+        // Dangling entries (left by truncation) are treated as absent and overwritten.
+        if self.position(&node.key).is_some() {
+            self.update_value(&node.key, node.value);
             return;
         }
 
         // Placement
-        let time_stamp = Self::current_time() + ttl_secs;
-        let node = Node {
-            key: key.clone(),
-            value,
-            counter: 1, // Start with 1 visibility
-            time_stamp,
+        if node.tti > 0 {
+            self.has_idle_entries = true;
+        }
+        self.has_priorities |= node.priority != Priority::Normal;
+        let key = node.key.clone();
+
+        // Reuse a dead slot in place, otherwise push the new node
+        let new_idx = match self.take_free_slot() {
+            Some(slot) => {
+                self.counter_sum = self.counter_sum.saturating_sub(self.frequency(slot));
+                self.arena[slot] = node;
+                self.garbage = self.garbage.saturating_sub(1);
+                slot
+            }
+            None => {
+                self.arena.push(node);
+                self.arena.len() - 1
+            }
         };
-        
-        // Push new node
-        self.arena.push(node);
-        let new_idx = self.arena.len() - 1;
+        self.total_weight += self.weigh(&self.arena[new_idx]);
         self.index.insert(key, new_idx);
-        self.counter_sum = self.counter_sum.saturating_add(1);
+        self.counter_sum = self.counter_sum.saturating_add(self.frequency(new_idx));
+        self.insertions += 1;
+        self.schedule_expiry(new_idx);
+        #[cfg(feature = "disk")]
+        self.log_insert(&self.arena[new_idx]);
+        self.emit(|| CacheEvent::Inserted(self.arena[new_idx].key.clone()));
 
         // Swap Rule: Immediately swap new node with node at evict_point + 1
         let target = self.evict_point + 1;
@@ -244,26 +2057,298 @@ where
         }
     }
 
-    /// C.3. The Double-Swap Delete
-    fn double_swap_delete(&mut self, key: &K) {
-        let idx = match self.index.get(key) {
-            Some(&i) if i < self.arena.len() && &self.arena[i].key == key => i,
-            _ => return,
+    // Helper: Drops stale free-list entries until a reusable slot (if any) is on top
+    fn has_free_slot(&mut self) -> bool {
+        while let Some(&slot) = self.free.last() {
+            if slot < self.arena.len() && !self.is_live(slot) {
+                return true;
+            }
+            self.free.pop();
+        }
+        false
+    }
+
+    // Helper: Pops a dead slot for in-place reuse
+    fn take_free_slot(&mut self) -> Option<usize> {
+        if self.has_free_slot() {
+            self.free.pop()
+        } else {
+            None
+        }
+    }
+
+    // Helper: Truncates at evict_point, releasing live victims (listener, weight).
+    // Without a listener, weigher or subscriber this is a plain truncate (no node is moved or cloned).
+    fn cliff_edge_truncate(&mut self) {
+        self.truncate_at(self.evict_point);
+    }
+
+    // Helper: Popularity of the node at `idx`: its counter, or the sketch
+    // estimate in sketch frequency mode
+    fn frequency(&self, idx: usize) -> u64 {
+        self.node_frequency(&self.arena[idx])
+    }
+
+    // Helper: `frequency` of a node, also one already taken out of the arena
+    fn node_frequency(&self, node: &Node<K, V>) -> u64 {
+        match &self.frequency {
+            Some(sketch) => u64::from(sketch.lock().estimate(&node.key)),
+            None => node.counter,
+        }
+    }
+
+    // Helper: Clone of the node at `idx` carrying its frequency as `counter`
+    // (so it survives a move between counter and sketch modes)
+    fn counted_clone(&self, idx: usize) -> Node<K, V> {
+        Node {
+            counter: self.frequency(idx),
+            ..self.arena[idx].clone()
+        }
+    }
+
+    // Helper: Moves the counter of a node carried over from elsewhere (warm
+    // restore, split) into the sketch in sketch frequency mode
+    fn absorb_counter(&self, node: &mut Node<K, V>) {
+        if let Some(sketch) = &self.frequency {
+            if node.counter > 0 {
+                sketch.lock().increment(&node.key, node.counter);
+            }
+            node.counter = 0;
+        }
+    }
+
+    // Helper: `frequency` summed over `range` (one sketch lock)
+    fn frequency_sum(&self, range: Range<usize>) -> u64 {
+        match &self.frequency {
+            Some(sketch) => {
+                let sketch = sketch.lock();
+                range
+                    .map(|idx| u64::from(sketch.estimate(&self.arena[idx].key)))
+                    .fold(0, u64::saturating_add)
+            }
+            None => range.map(|idx| self.arena[idx].counter).fold(0, u64::saturating_add),
+        }
+    }
+
+    // Helper: TinyLFU check; a new key must be more popular than the victim region.
+    // Pinned inserts always pass.
+    fn admit(&self, node: &Node<K, V>) -> bool {
+        let Some(sketch) = &self.admission else {
+            return true;
+        };
+        let key = &node.key;
+        let mut sketch = sketch.lock();
+        sketch.increment(key, 1);
+
+        if node.pinned
+            || self.arena.len() < self.capacity
+            || self.arena.is_empty()
+            || self.position(key).is_some()
+        {
+            return true;
+        }
+        // The cliff edge would drop [evict_point, len); its top slot is the
+        // strongest node the newcomer displaces
+        let victim = self.evict_point.min(self.arena.len() - 1);
+        sketch.estimate(key) > sketch.estimate(&self.arena[victim].key)
+    }
+
+    // Helper: Drops every slot from `start` on (pinned nodes excepted),
+    // releasing live victims
+    fn truncate_at(&mut self, start: usize) {
+        let start = self.rescue_pinned(start);
+        self.prioritize_victims(start);
+        if start >= self.arena.len() {
+            return;
+        }
+        self.evictions += (self.arena.len() - start) as u64;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            arena_len = self.arena.len(),
+            truncated = self.arena.len() - start,
+            "cliff-edge truncation"
+        );
+
+        // Every dropped slot (garbage included) leaves `counter_sum`
+        let dropped = self.frequency_sum(start..self.arena.len());
+        self.counter_sum = self.counter_sum.saturating_sub(dropped);
+
+        if self.listener.is_none() && self.weigher.is_none() && self.events.is_none() && self.watches.is_none() {
+            self.drop_tail(start);
+            return;
+        }
+
+        // Whole segments: nodes still shared with the mirror are not cloned
+        let evicted = self.arena.split_off_segments(start);
+        for (offset, node) in evicted.iter().flat_map(|segment| segment.iter()).enumerate() {
+            // Garbage (already expired/unlinked) nodes were released before
+            if self.index.get(&node.key) == Some(&(start + offset)) {
+                self.release_evicted(node);
+            }
+        }
+        self.discard(Garbage::Segments(evicted));
+    }
+
+    // Helper: Truncates the arena to `len`, handing the removed nodes to the
+    // dropper thread when deferred drop is on
+    fn drop_tail(&mut self, len: usize) {
+        let removed = self.arena.split_off_segments(len);
+        self.discard(Garbage::Segments(removed));
+    }
+
+    // Helper: Drops `garbage` on the dropper thread, or right here without one
+    fn discard(&self, garbage: CacheGarbage<K, V, S>) {
+        if let Some(dropper) = &self.dropper {
+            dropper.defer(garbage);
+        }
+    }
+
+    // Helper: Gathers live pinned nodes of `[start, len)` right after `start`
+    // and returns the cut point that keeps them
+    fn rescue_pinned(&mut self, start: usize) -> usize {
+        if self.pinned_count == 0 {
+            return start;
+        }
+        let mut cut = start;
+        for idx in start..self.arena.len() {
+            if self.arena[idx].pinned && self.is_live(idx) {
+                if idx != cut {
+                    self.swap_nodes(idx, cut);
+                }
+                cut += 1;
+            }
+        }
+        cut
+    }
+
+    // Helper: Applies a new capacity, evicting the tail beyond it
+    fn resize(&mut self, new_capacity: usize) {
+        self.capacity = new_capacity;
+        self.evict_point = self.evict_point.min(new_capacity);
+
+        if self.arena.len() > new_capacity {
+            self.truncate_at(new_capacity);
+        }
+    }
+
+    // Helper: Peels nodes off the tail until `incoming` fits the weight budget
+    fn evict_for_weight(&mut self, incoming: u64) {
+        if self.total_weight.saturating_add(incoming) <= self.max_weight {
+            return;
+        }
+
+        // Cliff edge first, then the coldest nodes one by one
+        if self.evict_point < self.arena.len() {
+            self.cliff_edge_truncate();
+        }
+        while self.total_weight.saturating_add(incoming) > self.max_weight && self.evict_coldest() {}
+    }
+
+    // Helper: Evicts the coldest slot that is not a live pinned node;
+    // false once only pinned nodes remain
+    fn evict_coldest(&mut self) -> bool {
+        let Some(victim) = (0..self.arena.len())
+            .rev()
+            .find(|&i| !self.arena[i].pinned || !self.is_live(i))
+        else {
+            return false;
+        };
+        let tail = self.arena.len() - 1;
+        self.swap_nodes(victim, tail);
+        let Some(node) = self.arena.pop() else { return false };
+        self.evictions += 1;
+        self.counter_sum = self.counter_sum.saturating_sub(self.node_frequency(&node));
+        if self.index.get(&node.key) == Some(&tail) {
+            self.index.remove(&node.key);
+            self.release_evicted(&node);
+        } else {
+            self.garbage = self.garbage.saturating_sub(1);
+        }
+        true
+    }
+
+    /// Evicts the coldest nodes down to the low watermark once the arena
+    /// reached the high one. Returns the number of slots dropped.
+    fn shed_to_low_watermark(&mut self) -> usize {
+        let Some((high, low)) = self.watermarks else {
+            return 0;
         };
+        let high = (self.capacity as f64 * high) as usize;
+        let low = (self.capacity as f64 * low) as usize;
+        if self.arena.len() < high {
+            return 0;
+        }
+
+        let mut shed = 0;
+        while self.live_len() > low && self.evict_coldest() {
+            shed += 1;
+        }
+        self.dirty |= shed > 0;
+        shed
+    }
+
+    // Helper: Accounting for a live node discarded by eviction
+    fn release_evicted(&mut self, node: &Node<K, V>) {
+        self.total_weight = self.total_weight.saturating_sub(self.weigh(node));
+        if let Some(listener) = &self.listener {
+            listener.on_evict(node);
+        }
+        self.emit(|| CacheEvent::Evicted(node.key.clone()));
+    }
+
+    // Helper: Weight of a node (0 when no weigher is configured)
+    fn weigh(&self, node: &Node<K, V>) -> u64 {
+        self.weigher
+            .as_ref()
+            .map_or(0, |weigher| weigher(&node.key, &node.value))
+    }
+
+    // Helper: Scans the arena once and double-swap deletes every live match
+    fn delete_where(&mut self, mut predicate: impl FnMut(&Node<K, V>) -> bool) -> usize {
+        let matched: Vec<K> = (0..self.arena.len())
+            .filter(|&i| self.is_live(i) && predicate(&self.arena[i]))
+            .map(|i| self.arena[i].key.clone())
+            .collect();
+
+        // Deletion swaps nodes around, so each key is resolved again
+        matched
+            .iter()
+            .filter(|key| self.double_swap_delete(*key).is_some())
+            .count()
+    }
+
+    /// C.3. The Double-Swap Delete
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip_all, fields(arena_len = self.arena.len()))
+    )]
+    fn double_swap_delete<Q>(&mut self, key: &Q) -> Option<Node<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.position(key)?;
 
         let target_swap_1 = self.evict_point + 1;
         
         // If the arena is too small to support the specific swap logic, just swap remove.
         if target_swap_1 >= self.arena.len() {
             // Fallback for small arenas/edge cases
-            self.arena.swap_remove(idx);
+            let moved_live = self.is_live(self.arena.len() - 1);
+            let node = self.arena.swap_remove(idx);
             if idx < self.arena.len() {
-                // swap_remove moved last to idx, update its index
-                let moved_key = self.arena[idx].key.clone();
-                self.index.insert(moved_key, idx);
+                // swap_remove moved last to idx: re-index it if live, garbage
+                // stays unlinked (as in `swap_nodes`) and its slot reusable
+                if moved_live {
+                    let moved_key = self.arena[idx].key.clone();
+                    self.index.insert(moved_key, idx);
+                } else {
+                    self.free.push(idx);
+                }
             }
-            self.index.remove(key);
-            return;
+            self.index.remove::<K>(&node.key);
+            self.release_deleted(&node);
+            return Some(node);
         }
 
         // Step 1: Swap arena[idx] with arena[evict_point + 1]
@@ -274,70 +2359,142 @@ where
         self.swap_nodes(target_swap_1, last_idx);
 
         // Step 3: Pop
-        if let Some(node) = self.arena.pop() {
-            self.index.remove(&node.key);
+        let node = self.arena.pop()?;
+        self.index.remove::<K>(&node.key);
+        self.release_deleted(&node);
+        Some(node)
+    }
+
+    // Helper: Accounting for a live node removed by delete
+    fn release_deleted(&mut self, node: &Node<K, V>) {
+        self.counter_sum = self.counter_sum.saturating_sub(self.node_frequency(node));
+        self.total_weight = self.total_weight.saturating_sub(self.weigh(node));
+        if node.pinned {
+            self.pinned_count -= 1;
+        }
+        if let Some(listener) = &self.listener {
+            listener.on_delete(node);
         }
+        #[cfg(feature = "disk")]
+        self.log_delete(&node.key);
+        self.publish_invalidation(&node.key);
+        self.emit(|| CacheEvent::Deleted(node.key.clone()));
     }
 
     /// C.4. Dynamic Membrane
-    fn update_evict_point(&mut self) {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "trace",
+            skip_all,
+            fields(arena_len = self.arena.len(), from = self.evict_point)
+        )
+    )]
+    fn update_evict_point(&mut self, hits: u64, misses: u64) {
         if self.arena.is_empty() {
             return;
         }
 
-        let avg = self.counter_sum / (self.arena.len() as u64).max(1);
-        let step_size = (self.capacity / 10).max(1);
-
-        // Check if average suggests expansion (simple heuristic based on activity)
-        // If the global sum is high relative to length, traffic is high, widen the safe zone.
-        // (Logic inferred from "Counter sum suggests avg has increased")
-        // Note: Real implementation might track previous avg to detect increase.
-        // Here we assume high average score implies we need more space protected.
-        
-        // Expansion logic: If evict point is small but avg is high, move evict_point forward (larger index)
-        if self.evict_point < self.capacity {
-             // Heuristic: If we are truncating too aggressively but nodes are hot
-            self.evict_point = (self.evict_point + step_size).min(self.capacity);
-        }
-
-        // Contraction: If the node AT evict_point is Strong (counter > avg)
-        // It "holds the line", effectively pushing the membrane back (or resisting move).
-        // Spec: "If node at evict_point has counter > avg... it holds the line."
-        // Interpreted as: If the border node is strong, we don't truncate it easily, 
-        // so we might actually reduce evict_point to tighten the circle or keep it there.
-        // HOWEVER, context implies "Membrane" moves to optimize cache.
-        // Let's implement Contraction as reducing `evict_point` if the boundary is weak?
-        // No, prompt says: "If node ... > avg (Strong Node), it holds the line."
-        // This usually implies preventing the evict_point from moving past it (shrinking the safe zone).
-        
-        // Let's implement a specific check:
-        if self.evict_point < self.arena.len() {
-            let boundary_node = &self.arena[self.evict_point];
-            if boundary_node.counter > avg {
-                // Strong node at border. 
-                // We do NOT contract (reduce index). We leave it or expand.
-            } else {
-                // Weak node at border. The membrane contracts (moves toward 0),
-                // making the "safe zone" smaller and "at risk" zone larger.
-                self.evict_point = self.evict_point.saturating_sub(step_size);
+        let step_size = ((self.capacity as f64 * self.step_ratio) as usize).max(1);
+        if let Some(sketch) = &self.frequency {
+            // A reset halved every estimate the running total was built from
+            let resets = sketch.lock().resets();
+            if resets != self.sketch_resets {
+                self.counter_sum = self.frequency_sum(0..self.arena.len());
+                self.sketch_resets = resets;
             }
         }
-        
+        let counter_sum = self.counter_sum;
+        let counter_at = |idx: usize| (idx < self.arena.len()).then(|| self.frequency(idx));
+        let view = MembraneView::new(
+            self.evict_point,
+            self.arena.len(),
+            self.capacity,
+            counter_sum,
+            step_size,
+            &counter_at,
+        )
+        .with_reads(hits, misses);
+        let next = self.membrane.lock().next_evict_point(&view);
+
         // Safety: Ensure evict_point stays within bounds relative to capacity
-        if self.evict_point > self.capacity {
-            self.evict_point = self.capacity;
+        let previous = self.evict_point;
+        self.evict_point = next.min(self.capacity);
+
+        // Weight threshold: the protected zone may not outweigh the budget
+        if self.weigher.is_some() {
+            let mut protected = 0u64;
+            for i in 0..self.evict_point.min(self.arena.len()) {
+                if self.position(&self.arena[i].key) != Some(i) {
+                    continue; // Garbage carries no weight
+                }
+                protected = protected.saturating_add(self.weigh(&self.arena[i]));
+                if protected > self.max_weight {
+                    self.evict_point = i;
+                    break;
+                }
+            }
         }
+        self.dirty |= self.evict_point != previous;
     }
 
     /// C.5. Updates
-    fn update_value(&mut self, key: &K, value: V) {
-         if let Some(&idx) = self.index.get(key) {
-             if idx < self.arena.len() && &self.arena[idx].key == key {
-                 self.arena[idx].value = value;
-                 // Constraint: Do NOT reset counter or rank (index).
-                 // Done.
-             }
-         }
+    fn update_value<Q>(&mut self, key: &Q, value: V)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if let Some(idx) = self.position(key) {
+            self.begin_update(idx);
+            self.arena[idx].value = value;
+            // Constraint: Do NOT reset counter or rank (index).
+            self.finish_update(idx);
+        }
+    }
+
+    // Helper: Opens an in-place value change at `idx`: bumps the version and
+    // takes the node's weight out until `finish_update` re-weighs it
+    fn begin_update(&mut self, idx: usize) {
+        self.total_weight = self.total_weight.saturating_sub(self.weigh(&self.arena[idx]));
+        self.arena[idx].version = self.arena[idx].version.wrapping_add(1);
+    }
+
+    // Helper: Closes an in-place value change: re-weighs the node, logs it,
+    // publishes the invalidation and emits `Updated`
+    fn finish_update(&mut self, idx: usize) {
+        self.total_weight += self.weigh(&self.arena[idx]);
+        #[cfg(feature = "disk")]
+        self.log_update(idx);
+        self.publish_invalidation(&self.arena[idx].key);
+        self.emit(|| CacheEvent::Updated(self.arena[idx].key.clone()));
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_swap_delete_leaves_garbage_tail_unindexed() {
+        // evict_point + 1 is past the arena, so delete takes the swap_remove fallback
+        let (dual, _rx) = DualCacheBuilder::new(4).build::<u32, u32>();
+        let mut cache = dual.main.lock();
+        for key in 1..=3 {
+            cache.gatsby_insert(key, key, 60);
+        }
+        cache.invalidate_expired(2);
+
+        assert_eq!(cache.double_swap_delete(&1).map(|node| node.key), Some(1));
+        assert_eq!(cache.arena.len(), 2);
+        assert_eq!(cache.arena[0].key, 3);
+        assert_eq!(cache.position(&3), None);
+        assert_eq!(cache.position(&2), Some(1));
+
+        // The garbage slot is reused in place
+        cache.gatsby_insert(4, 4, 60);
+        assert_eq!(cache.arena.len(), 2);
+        assert_eq!(cache.position(&4), Some(0));
+        drop(cache);
+        assert!(dual.validate().is_ok());
     }
 }
 //code support by gemini 3.0
\ No newline at end of file
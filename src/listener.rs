@@ -0,0 +1,21 @@
+use crate::Node;
+
+// -----------------------------------------------------------------------------
+// Eviction Listeners
+// -----------------------------------------------------------------------------
+
+/// Hooks invoked by the write path when a live node leaves the cache.
+/// Registered via `DualCache::set_listener`.
+///
+/// Callbacks run synchronously while the main lock is held: keep them short,
+/// and never call back into the same `DualCache` from inside a hook.
+pub trait CacheListener<K, V>: Send + Sync {
+    /// Cliff-edge truncation discarded the node.
+    fn on_evict(&self, _node: &Node<K, V>) {}
+
+    /// The node was invalidated because its TTL or idle window passed.
+    fn on_expire(&self, _node: &Node<K, V>) {}
+
+    /// The node was removed by an explicit delete.
+    fn on_delete(&self, _node: &Node<K, V>) {}
+}
@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::Mutex;
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Read-Through Loading (Singleflight)
+// -----------------------------------------------------------------------------
+
+/// Fetches a missing value from the backing store. `None` means the key
+/// does not exist there; nothing is cached in that case.
+pub trait Loader<K, V>: Send + Sync {
+    fn load(&self, key: &K) -> Option<V>;
+}
+
+impl<K, V, F> Loader<K, V> for F
+where
+    F: Fn(&K) -> Option<V> + Send + Sync,
+{
+    fn load(&self, key: &K) -> Option<V> {
+        self(key)
+    }
+}
+
+/// Async counterpart of `Loader`, used by `LoadingDualCache::get_async`.
+#[cfg(feature = "async")]
+pub trait AsyncLoader<K, V>: Send + Sync {
+    fn load(&self, key: K) -> impl Future<Output = Option<V>> + Send;
+}
+
+#[cfg(feature = "async")]
+impl<K, V, F, Fut> AsyncLoader<K, V> for F
+where
+    F: Fn(K) -> Fut + Send + Sync,
+    Fut: Future<Output = Option<V>> + Send,
+{
+    fn load(&self, key: K) -> impl Future<Output = Option<V>> + Send {
+        self(key)
+    }
+}
+
+type Slots<K, S> = Mutex<HashMap<K, Arc<S>>>;
+
+/// A `DualCache` that fills misses through a `Loader`.
+/// Concurrent misses for one key share a single load; a loaded value is
+/// inserted with the configured TTL and committed before it is returned.
+pub struct LoadingDualCache<K, V, L>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V>>,
+    loader: L,
+    ttl_secs: u64,
+    in_flight: Slots<K, OnceLock<Option<V>>>,
+    #[cfg(feature = "async")]
+    in_flight_async: Slots<K, tokio::sync::OnceCell<Option<V>>>,
+}
+
+impl<K, V, L> LoadingDualCache<K, V, L>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    pub fn new(cache: Arc<DualCache<K, V>>, loader: L, ttl_secs: u64) -> Self {
+        Self {
+            cache,
+            loader,
+            ttl_secs,
+            in_flight: Mutex::new(HashMap::new()),
+            #[cfg(feature = "async")]
+            in_flight_async: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The wrapped cache (for writes, maintenance, the Daemon, ...).
+    pub fn cache(&self) -> &Arc<DualCache<K, V>> {
+        &self.cache
+    }
+
+    /// Returns the cached value, loading it on a miss.
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        L: Loader<K, V>,
+    {
+        if let Some(value) = self.cache.get(key) {
+            return Some(value);
+        }
+
+        let slot = self.slot(&self.in_flight, key, OnceLock::new);
+        // Other callers for this key block here until the first load finishes
+        let value = slot.get_or_init(|| self.load_and_insert(key, self.loader.load(key))).clone();
+        self.retire(&self.in_flight, key, &slot);
+        value
+    }
+
+    /// Async `get`: concurrent misses await one shared load.
+    #[cfg(feature = "async")]
+    pub async fn get_async(&self, key: &K) -> Option<V>
+    where
+        L: AsyncLoader<K, V>,
+    {
+        if let Some(value) = self.cache.get(key) {
+            return Some(value);
+        }
+
+        let slot = self.slot(&self.in_flight_async, key, tokio::sync::OnceCell::new);
+        let value = slot
+            .get_or_init(|| async {
+                let loaded = self.loader.load(key.clone()).await;
+                self.load_and_insert(key, loaded)
+            })
+            .await
+            .clone();
+        self.retire(&self.in_flight_async, key, &slot);
+        value
+    }
+
+    // Helper: Caches a loaded value and makes it visible to readers
+    fn load_and_insert(&self, key: &K, loaded: Option<V>) -> Option<V> {
+        let value = loaded?;
+        self.cache.insert(key.clone(), value.clone(), self.ttl_secs);
+        self.cache.commit();
+        Some(value)
+    }
+
+    // Helper: The shared in-flight slot for `key`
+    fn slot<S>(&self, slots: &Slots<K, S>, key: &K, new: impl FnOnce() -> S) -> Arc<S> {
+        slots
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(new()))
+            .clone()
+    }
+
+    // Helper: Drops the slot unless a newer load already replaced it
+    fn retire<S>(&self, slots: &Slots<K, S>, key: &K, slot: &Arc<S>) {
+        let mut slots = slots.lock();
+        if slots.get(key).is_some_and(|current| Arc::ptr_eq(current, slot)) {
+            slots.remove(key);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use crate::{Cache, DualCache, Probe};
+
+// -----------------------------------------------------------------------------
+// Thread-Local Readers (Cached Snapshot)
+// -----------------------------------------------------------------------------
+
+/// A reader that keeps its own `Arc` of the mirror, so a lookup is a plain
+/// pointer dereference instead of an `ArcSwap::load`.
+///
+/// Meant to be owned by one thread (e.g. in a `thread_local!`). By default
+/// the snapshot is replaced once a newer generation is published, checked
+/// with one atomic load per read; with `refresh_every(n)` it is
+/// only checked every `n` reads, so reads may lag by up to `n` lookups.
+/// Holding a snapshot keeps that generation's segments alive until refresh.
+pub struct LocalReader<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V, S>>,
+    snapshot: Arc<Cache<K, V, S>>,
+    replica: usize,
+    refresh_every: u64,
+    reads: u64,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// A `LocalReader` starting from the current mirror.
+    pub fn local_reader(self: &Arc<Self>) -> LocalReader<K, V, S> {
+        self.local_reader_on(0)
+    }
+
+    // Helper: A `LocalReader` fed from mirror replica `replica`
+    pub(crate) fn local_reader_on(self: &Arc<Self>, replica: usize) -> LocalReader<K, V, S> {
+        LocalReader {
+            cache: Arc::clone(self),
+            snapshot: self.replica(replica).load_full(),
+            replica,
+            refresh_every: 0,
+            reads: 0,
+        }
+    }
+}
+
+impl<K, V, S> LocalReader<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Only look for a newer generation every `reads` lookups (0 = every lookup).
+    pub fn refresh_every(mut self, reads: u64) -> Self {
+        self.refresh_every = reads;
+        self
+    }
+
+    /// Same as `DualCache::get`, against the cached snapshot.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_with(key, V::clone)
+    }
+
+    /// Same as `DualCache::get_with`, against the cached snapshot.
+    pub fn get_with<Q, R>(&mut self, key: &Q, read: impl FnOnce(&V) -> R) -> Option<R>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.maybe_refresh();
+        self.cache.read_in(&self.snapshot, key, |node| read(&node.value))
+    }
+
+    /// Same as `DualCache::peek`, against the cached snapshot.
+    pub fn peek<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.maybe_refresh();
+        match self.cache.probe(&self.snapshot, key) {
+            Probe::Hit(node) | Probe::Stale(node) => Some(node.value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Generation of the cached snapshot.
+    pub fn generation(&self) -> u64 {
+        self.snapshot.generation
+    }
+
+    /// Reloads the snapshot now, regardless of the refresh schedule.
+    pub fn refresh(&mut self) {
+        self.snapshot = self.cache.replica(self.replica).load_full();
+        self.reads = 0;
+    }
+
+    // Helper: Swaps in the current mirror when the schedule says to look and
+    // a newer generation was published
+    fn maybe_refresh(&mut self) {
+        if self.refresh_every > 0 {
+            self.reads += 1;
+            if self.reads < self.refresh_every {
+                return;
+            }
+            self.reads = 0;
+        }
+        if self.cache.published.load(Ordering::Acquire) != self.snapshot.generation {
+            self.refresh();
+        }
+    }
+}
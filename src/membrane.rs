@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+
+// -----------------------------------------------------------------------------
+// Dynamic Membrane Policies (C.4)
+// -----------------------------------------------------------------------------
+
+/// Read-only view of the membrane state handed to an `EvictPointPolicy`.
+pub struct MembraneView<'a> {
+    /// Current membrane position.
+    pub evict_point: usize,
+    /// Physical arena length (including not-yet-truncated garbage).
+    pub len: usize,
+    pub capacity: usize,
+    pub counter_sum: u64,
+    /// Configured step (`capacity * membrane_step_ratio`, at least 1).
+    pub step_size: usize,
+    /// Read-path hits so far (cumulative, relaxed).
+    pub hits: u64,
+    /// Read-path misses so far (cumulative, relaxed).
+    pub misses: u64,
+    counter_at: &'a dyn Fn(usize) -> Option<u64>,
+}
+
+impl<'a> MembraneView<'a> {
+    pub(crate) fn new(
+        evict_point: usize,
+        len: usize,
+        capacity: usize,
+        counter_sum: u64,
+        step_size: usize,
+        counter_at: &'a dyn Fn(usize) -> Option<u64>,
+    ) -> Self {
+        Self {
+            evict_point,
+            len,
+            capacity,
+            counter_sum,
+            step_size,
+            hits: 0,
+            misses: 0,
+            counter_at,
+        }
+    }
+
+    pub(crate) fn with_reads(mut self, hits: u64, misses: u64) -> Self {
+        self.hits = hits;
+        self.misses = misses;
+        self
+    }
+
+    /// `counter_sum / len`.
+    pub fn average(&self) -> u64 {
+        self.counter_sum / (self.len as u64).max(1)
+    }
+
+    /// Hit counter of the node at rank `idx`, if in bounds.
+    pub fn counter_at(&self, idx: usize) -> Option<u64> {
+        (self.counter_at)(idx)
+    }
+}
+
+/// Decides where the membrane (`evict_point`) moves on each maintenance pass.
+/// The returned value is clamped to `capacity` (and to the weight budget, if any).
+pub trait EvictPointPolicy: Send {
+    fn next_evict_point(&mut self, view: &MembraneView<'_>) -> usize;
+}
+
+/// The default membrane dynamics (C.4). Each maintenance pass compares the
+/// average counter with the previous pass:
+/// - the membrane expands by one step when the average rose (traffic is
+///   heating up) and the boundary node is strong (counter above average);
+/// - it contracts by one step when the boundary node is weak and the average
+///   did not rise, exposing more of the tail to the cliff edge.
+///
+/// A move only happens after its condition held for `hysteresis` consecutive
+/// passes, so the membrane does not oscillate on every cycle.
+#[derive(Clone, Copy, Debug)]
+pub struct ViscousMembrane {
+    hysteresis: u32,
+    previous_average: Option<u64>,
+    expand_streak: u32,
+    contract_streak: u32,
+}
+
+impl ViscousMembrane {
+    /// Default dynamics with a hysteresis of 2 passes.
+    pub fn new() -> Self {
+        Self::with_hysteresis(2)
+    }
+
+    /// Consecutive passes a condition must hold before the membrane moves
+    /// (`1` reacts on every pass).
+    pub fn with_hysteresis(passes: u32) -> Self {
+        Self {
+            hysteresis: passes.max(1),
+            previous_average: None,
+            expand_streak: 0,
+            contract_streak: 0,
+        }
+    }
+}
+
+impl Default for ViscousMembrane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EvictPointPolicy for ViscousMembrane {
+    fn next_evict_point(&mut self, view: &MembraneView<'_>) -> usize {
+        let avg = view.average();
+        let rising = self.previous_average.is_some_and(|previous| avg > previous);
+        self.previous_average = Some(avg);
+
+        // The node AT evict_point holds the line if its counter beats the average.
+        // A membrane sitting past the last node has no boundary to defend.
+        let weak_boundary = view.counter_at(view.evict_point).is_some_and(|counter| counter <= avg);
+
+        if rising && !weak_boundary && view.evict_point < view.capacity {
+            self.expand_streak += 1;
+            self.contract_streak = 0;
+        } else if weak_boundary && !rising {
+            self.contract_streak += 1;
+            self.expand_streak = 0;
+        } else {
+            // Conflicting or neutral signals: hold the line
+            self.expand_streak = 0;
+            self.contract_streak = 0;
+        }
+
+        if self.expand_streak >= self.hysteresis {
+            self.expand_streak = 0;
+            return (view.evict_point + view.step_size).min(view.capacity);
+        }
+        if self.contract_streak >= self.hysteresis {
+            self.contract_streak = 0;
+            return view.evict_point.saturating_sub(view.step_size);
+        }
+        view.evict_point
+    }
+}
+
+/// Pins the membrane at a fixed fraction of capacity (e.g. 0.8 protects 80%).
+#[derive(Clone, Copy, Debug)]
+pub struct FixedRatio(pub f64);
+
+impl EvictPointPolicy for FixedRatio {
+    fn next_evict_point(&mut self, view: &MembraneView<'_>) -> usize {
+        (view.capacity as f64 * self.0.clamp(0.0, 1.0)) as usize
+    }
+}
+
+/// Moves the membrane by the hit ratio observed over the last `window`
+/// maintenance passes: above `high` the protected zone shrinks by one step
+/// (hits are plentiful, so free room for admission); below `low` it grows by
+/// one step (misses are spiking, so protect more of the working set).
+#[derive(Clone, Debug)]
+pub struct HitRateMembrane {
+    window: usize,
+    low: f64,
+    high: f64,
+    last_totals: Option<(u64, u64)>,
+    // (hits, misses) per pass, oldest first
+    history: VecDeque<(u64, u64)>,
+}
+
+impl HitRateMembrane {
+    pub fn new(window: usize, low: f64, high: f64) -> Self {
+        Self {
+            window: window.max(1),
+            low,
+            high,
+            last_totals: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Hit ratio across the current window, if any read was observed.
+    pub fn window_hit_ratio(&self) -> Option<f64> {
+        let (hits, misses) = self
+            .history
+            .iter()
+            .fold((0, 0), |(h, m), &(hits, misses)| (h + hits, m + misses));
+        let total = hits + misses;
+        (total > 0).then(|| hits as f64 / total as f64)
+    }
+}
+
+impl Default for HitRateMembrane {
+    /// 10 passes, shrinking above 95% hits and growing below 80%.
+    fn default() -> Self {
+        Self::new(10, 0.8, 0.95)
+    }
+}
+
+impl EvictPointPolicy for HitRateMembrane {
+    fn next_evict_point(&mut self, view: &MembraneView<'_>) -> usize {
+        let (hits, misses) = self.last_totals.unwrap_or((view.hits, view.misses));
+        self.last_totals = Some((view.hits, view.misses));
+        self.history.push_back((view.hits.saturating_sub(hits), view.misses.saturating_sub(misses)));
+        if self.history.len() > self.window {
+            self.history.pop_front();
+        }
+
+        match self.window_hit_ratio() {
+            Some(ratio) if ratio > self.high => view.evict_point.saturating_sub(view.step_size),
+            Some(ratio) if ratio < self.low => (view.evict_point + view.step_size).min(view.capacity),
+            _ => view.evict_point,
+        }
+    }
+}
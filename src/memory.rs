@@ -0,0 +1,175 @@
+use std::hash::{BuildHasher, Hash};
+use std::mem::size_of;
+use std::sync::Arc;
+
+use crate::{Cache, DualCache, Node};
+
+// -----------------------------------------------------------------------------
+// Memory Estimation
+// -----------------------------------------------------------------------------
+
+/// Heap bytes owned by a value, beyond its inline `size_of`. Implemented for
+/// the std types keys and values are usually built from; implement it for
+/// your own to get `estimated_memory_bytes_deep`.
+pub trait MemSize {
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! inline_only {
+    ($($ty:ty),*) => {
+        $(impl MemSize for $ty {
+            fn heap_size(&self) -> usize {
+                0
+            }
+        })*
+    };
+}
+
+inline_only!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ());
+
+impl MemSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: MemSize> MemSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(MemSize::heap_size).sum::<usize>()
+    }
+}
+
+impl<T: MemSize> MemSize for Box<T> {
+    fn heap_size(&self) -> usize {
+        size_of::<T>() + (**self).heap_size()
+    }
+}
+
+/// Counted in full for every handle (the estimate cannot see sharing).
+impl<T: MemSize> MemSize for Arc<T> {
+    fn heap_size(&self) -> usize {
+        size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T: MemSize> MemSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, MemSize::heap_size)
+    }
+}
+
+impl<A: MemSize, B: MemSize> MemSize for (A, B) {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
+}
+
+/// Called with the estimate when it crosses the threshold of `set_memory_alarm`.
+pub type MemoryCallback = Arc<dyn Fn(usize) + Send + Sync>;
+
+// Threshold check run by `maintenance`; fires once per upward crossing
+#[derive(Clone)]
+pub(crate) struct MemoryAlarm<K, V> {
+    threshold: usize,
+    // Heap bytes per entry (deep alarms); None estimates inline sizes only
+    heap: Option<fn(&K, &V) -> usize>,
+    callback: MemoryCallback,
+    tripped: bool,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Rough bytes held by `main`: arena slots (garbage included), index
+    /// entries and the free list, from inline sizes. Values that own heap
+    /// memory (strings, buffers) need `estimated_memory_bytes_deep`.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        self.main.lock().estimated_memory(None)
+    }
+
+    /// `estimated_memory_bytes` plus the heap bytes of every live key, value
+    /// and tag list. Walks the arena under the main lock.
+    pub fn estimated_memory_bytes_deep(&self) -> usize
+    where
+        K: MemSize,
+        V: MemSize,
+    {
+        self.main.lock().estimated_memory(Some(deep_heap_size::<K, V>))
+    }
+
+    /// Calls `callback` with the estimate (`estimated_memory_bytes`) when
+    /// `maintenance` finds it above `threshold_bytes`; it fires again only
+    /// after the estimate dropped back below. Replaces any previous alarm.
+    pub fn set_memory_alarm(&self, threshold_bytes: usize, callback: impl Fn(usize) + Send + Sync + 'static) {
+        self.main.lock().memory_alarm = Some(MemoryAlarm {
+            threshold: threshold_bytes,
+            heap: None,
+            callback: Arc::new(callback),
+            tripped: false,
+        });
+    }
+
+    /// `set_memory_alarm` against `estimated_memory_bytes_deep`, so each
+    /// check walks the arena.
+    pub fn set_memory_alarm_deep(&self, threshold_bytes: usize, callback: impl Fn(usize) + Send + Sync + 'static)
+    where
+        K: MemSize,
+        V: MemSize,
+    {
+        self.main.lock().memory_alarm = Some(MemoryAlarm {
+            threshold: threshold_bytes,
+            heap: Some(deep_heap_size::<K, V>),
+            callback: Arc::new(callback),
+            tripped: false,
+        });
+    }
+
+    pub fn clear_memory_alarm(&self) {
+        self.main.lock().memory_alarm = None;
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Inline footprint, plus `heap` of every live node when given
+    fn estimated_memory(&self, heap: Option<fn(&K, &V) -> usize>) -> usize {
+        // One hashbrown control byte per index entry
+        let index = self.index.len() * (size_of::<(K, usize)>() + 1);
+        let inline = self.arena.len() * size_of::<Node<K, V>>()
+            + index
+            + self.free.capacity() * size_of::<usize>();
+        let Some(heap) = heap else { return inline };
+        (0..self.arena.len())
+            .filter(|&i| self.is_live(i))
+            .map(|i| {
+                let node = &self.arena[i];
+                heap(&node.key, &node.value) + node.tags.heap_size()
+            })
+            .fold(inline, usize::saturating_add)
+    }
+
+    // Helper: Fires the memory alarm on an upward threshold crossing
+    pub(crate) fn check_memory_alarm(&mut self) {
+        let Some(alarm) = &self.memory_alarm else { return };
+        let estimate = self.estimated_memory(alarm.heap);
+        let above = estimate > alarm.threshold;
+        if above && !alarm.tripped {
+            (alarm.callback)(estimate);
+        }
+        if let Some(alarm) = &mut self.memory_alarm {
+            alarm.tripped = above;
+        }
+    }
+}
+
+// Helper: Heap bytes of one entry, for deep estimates
+fn deep_heap_size<K: MemSize, V: MemSize>(key: &K, value: &V) -> usize {
+    key.heap_size() + value.heap_size()
+}
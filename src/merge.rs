@@ -0,0 +1,158 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, CacheEvent, DualCache, Node};
+
+// -----------------------------------------------------------------------------
+// Merging Caches (Consolidation)
+// -----------------------------------------------------------------------------
+
+/// Which entry wins when `merge_from` finds a key in both caches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// The entry already here stays.
+    #[default]
+    KeepExisting,
+    /// The incoming entry replaces it.
+    Overwrite,
+    /// The entry with the higher counter (the more popular one) wins.
+    MaxCounter,
+    /// The entry with the later deadline (`time_stamp`) wins.
+    NewestTimestamp,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Imports every live, unexpired entry of `other` (e.g. a per-worker
+    /// cache on scale-down) under one lock and one commit. New keys keep
+    /// their counter, deadlines, tags, pin and priority, and go below the
+    /// existing entries in `other`'s rank order (bans and the admission
+    /// filter still apply; one cliff-edge cut makes room). On a conflict
+    /// `strategy` picks the winner; a winning incoming entry brings its
+    /// value and deadlines, and the counter becomes the higher of the two.
+    /// Like `warm_from`, the merge is not reported per key to listeners,
+    /// the event stream or the op log; key watches see it on the commit.
+    /// Both caches should share a clock, since deadlines are copied as they
+    /// are. `other` is only read. Returns the number of entries imported or replaced.
+    pub fn merge_from<S2>(&self, other: &DualCache<K, V, S2>, strategy: MergeStrategy) -> usize
+    where
+        S2: BuildHasher + Clone + Send + Sync + 'static,
+    {
+        if std::ptr::addr_eq(self, other) {
+            return 0;
+        }
+        // Copied out first: never hold both main locks
+        let incoming = other.main.lock().live_nodes_by_rank();
+
+        let mut guard = self.main.lock();
+        let mut fresh = Vec::new();
+        let mut replaced = 0;
+        for node in incoming {
+            match guard.unexpired_position(&node.key) {
+                Some(idx) => replaced += usize::from(guard.merge_conflict(idx, node, strategy)),
+                None if guard.is_banned(&node.key) || !guard.admit(&node) => guard.rejections += 1,
+                None => fresh.push(node),
+            }
+        }
+        let imported = guard.merge_fresh(fresh);
+        self.publish(&mut guard);
+        replaced + imported
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Clones of the live, unexpired nodes, hottest first
+    fn live_nodes_by_rank(&self) -> Vec<Node<K, V>> {
+        let now = self.current_time();
+        (0..self.arena.len())
+            .filter(|&i| self.is_live(i) && !self.arena[i].is_expired(now))
+            .map(|i| self.counted_clone(i))
+            .collect()
+    }
+
+    // Helper: Applies `incoming` over the live node at `idx` if it wins;
+    // written in place, so the merge stays one silent write
+    fn merge_conflict(&mut self, idx: usize, incoming: Node<K, V>, strategy: MergeStrategy) -> bool {
+        let wins = match strategy {
+            MergeStrategy::KeepExisting => false,
+            MergeStrategy::Overwrite => true,
+            MergeStrategy::MaxCounter => incoming.counter > self.frequency(idx),
+            MergeStrategy::NewestTimestamp => incoming.time_stamp > self.arena[idx].time_stamp,
+        };
+        if !wins {
+            return false;
+        }
+
+        self.raise_counter(idx, incoming.counter);
+        self.total_weight = self.total_weight.saturating_sub(self.weigh(&self.arena[idx]));
+        self.has_idle_entries |= incoming.tti > 0;
+        let node = &mut self.arena[idx];
+        node.value = incoming.value;
+        node.version = node.version.wrapping_add(1);
+        node.tti = incoming.tti;
+        node.last_access = incoming.last_access;
+        node.soft_ttl = incoming.soft_ttl;
+        node.soft_deadline = incoming.soft_deadline;
+        node.tags = incoming.tags;
+        self.total_weight += self.weigh(&self.arena[idx]);
+        self.move_deadline(idx, incoming.time_stamp);
+        self.publish_invalidation(&self.arena[idx].key);
+        self.record_watched(idx);
+        true
+    }
+
+    // Helper: Places new keys (hottest first) below the existing entries in
+    // that order, after one cut sized for all of them. Returns how many fit.
+    fn merge_fresh(&mut self, mut nodes: Vec<Node<K, V>>) -> usize {
+        nodes.truncate(self.capacity);
+        let room = self.capacity.saturating_sub(self.arena.len());
+        if nodes.len() > room {
+            let cut = self.evict_point.min(self.capacity - nodes.len());
+            self.truncate_at(cut);
+        }
+        if let Some(sketch) = &self.frequency {
+            // Sketch frequency mode: the sketch carries the imported counts
+            let mut sketch = sketch.lock();
+            for node in &mut nodes {
+                sketch.increment(&node.key, node.counter.max(1));
+                node.counter = 0;
+            }
+        }
+        for node in &mut nodes {
+            node.version = 0;
+        }
+
+        let from = self.arena.len();
+        self.adopt(nodes);
+        (from..self.arena.len()).for_each(|idx| self.record_watched(idx));
+        self.arena.len() - from
+    }
+
+    // Helper: Lifts the popularity of the node at `idx` to at least `counter`
+    fn raise_counter(&mut self, idx: usize, counter: u64) {
+        let current = self.frequency(idx);
+        if counter <= current {
+            return;
+        }
+        match &self.frequency {
+            Some(sketch) => sketch.lock().increment(&self.arena[idx].key, counter - current),
+            None => self.arena[idx].counter = counter,
+        }
+        self.counter_sum = self.counter_sum.saturating_add(self.frequency(idx).saturating_sub(current));
+    }
+
+    // Helper: Flags the key at `idx` for key watches at the next commit
+    fn record_watched(&self, idx: usize) {
+        if let Some(watches) = &self.watches {
+            watches.record(&CacheEvent::Updated(self.arena[idx].key.clone()));
+        }
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "prometheus")]
+use std::fmt::Write as _;
+use std::hash::{BuildHasher, Hash};
+#[cfg(feature = "metrics")]
+use std::time::Duration;
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Metrics Export (feature = "metrics" / "prometheus")
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Publishes the current statistics through the `metrics` facade.
+    /// Called by the Daemon after every commit; the read path itself never
+    /// touches the recorder.
+    #[cfg(feature = "metrics")]
+    pub fn export_metrics(&self) {
+        let stats = self.stats();
+        metrics::counter!("dualcache_hits_total").absolute(stats.hits);
+        metrics::counter!("dualcache_misses_total").absolute(stats.misses);
+        metrics::counter!("dualcache_insertions_total").absolute(stats.insertions);
+        metrics::counter!("dualcache_evictions_total").absolute(stats.evictions);
+        metrics::counter!("dualcache_expirations_total").absolute(stats.expirations);
+        metrics::counter!("dualcache_rejections_total").absolute(stats.rejections);
+        metrics::counter!("dualcache_dropped_signals_total").absolute(stats.dropped_signals);
+        metrics::gauge!("dualcache_occupancy").set(stats.occupancy as f64);
+        metrics::gauge!("dualcache_capacity").set(stats.capacity as f64);
+        metrics::gauge!("dualcache_total_weight").set(stats.total_weight as f64);
+        metrics::gauge!("dualcache_evict_point").set(self.main.lock().evict_point as f64);
+        metrics::gauge!("dualcache_channel_depth").set(stats.channel_len as f64);
+    }
+
+    /// Current statistics in the Prometheus text exposition format.
+    #[cfg(feature = "prometheus")]
+    pub fn prometheus_text(&self) -> String {
+        let stats = self.stats();
+        let evict_point = self.main.lock().evict_point;
+
+        let mut out = String::new();
+        let mut emit = |name: &str, kind: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP dualcache_{name} {help}");
+            let _ = writeln!(out, "# TYPE dualcache_{name} {kind}");
+            let _ = writeln!(out, "dualcache_{name} {value}");
+        };
+        emit("hits_total", "counter", "Reads served from the mirror.", stats.hits);
+        emit("misses_total", "counter", "Reads that found no live entry.", stats.misses);
+        emit("insertions_total", "counter", "New keys inserted.", stats.insertions);
+        emit("evictions_total", "counter", "Slots discarded by eviction.", stats.evictions);
+        emit("expirations_total", "counter", "Entries invalidated by TTL.", stats.expirations);
+        emit("rejections_total", "counter", "Inserts refused by admission.", stats.rejections);
+        emit("dropped_signals_total", "counter", "Read signals lost to backpressure.", stats.dropped_signals);
+        emit("occupancy", "gauge", "Arena slots in main.", stats.occupancy as u64);
+        emit("capacity", "gauge", "Configured capacity.", stats.capacity as u64);
+        emit("total_weight", "gauge", "Weight of live entries.", stats.total_weight);
+        emit("evict_point", "gauge", "Membrane position.", evict_point as u64);
+        emit("channel_depth", "gauge", "Queued read signals.", stats.channel_len as u64);
+        out
+    }
+}
+
+// Helper: Commit latency histogram, recorded by every publish
+#[cfg(feature = "metrics")]
+pub(crate) fn record_commit(elapsed: Duration) {
+    metrics::histogram!("dualcache_commit_seconds").record(elapsed.as_secs_f64());
+}
@@ -0,0 +1,295 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::future::{Future, ready};
+use std::hash::{BuildHasher, Hash};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use ::tower::{Layer, Service};
+use parking_lot::Mutex;
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// tower Response Caching (feature = "tower")
+// -----------------------------------------------------------------------------
+
+/// How long a response cached by `CacheLayer` is served.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// Fresh for this long: served without calling the inner service.
+    pub ttl: Duration,
+    /// Then still served for this long while one background request
+    /// revalidates it. Zero turns stale-while-revalidate off.
+    pub stale_while_revalidate: Duration,
+}
+
+impl CachePolicy {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            stale_while_revalidate: Duration::ZERO,
+        }
+    }
+
+    pub fn stale_while_revalidate(mut self, window: Duration) -> Self {
+        self.stale_while_revalidate = window;
+        self
+    }
+}
+
+impl Default for CachePolicy {
+    /// One minute, no stale window.
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60))
+    }
+}
+
+type KeyFn<Req, K> = Arc<dyn Fn(&Req) -> Option<K> + Send + Sync>;
+type PolicyFn<Req> = Arc<dyn Fn(&Req) -> CachePolicy + Send + Sync>;
+type CacheableFn<Resp> = Arc<dyn Fn(&Resp) -> bool + Send + Sync>;
+
+// What every service built by one layer shares
+struct Shared<Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, Resp>>,
+    key: KeyFn<Req, K>,
+    policy: PolicyFn<Req>,
+    cacheable: CacheableFn<Resp>,
+    // Keys with a background revalidation in flight
+    revalidating: Mutex<HashSet<K>>,
+}
+
+/// `tower::Layer` that answers requests from a `DualCache` of responses.
+/// The key function picks what is cached: `None` passes the request
+/// through untouched (e.g. non-GET methods). A miss calls the inner service
+/// and caches its response under the policy chosen for the request, so TTLs
+/// can differ per route. Once past its TTL, a response is served stale while
+/// the current request revalidates it in a spawned tokio task (one per key).
+///
+/// Responses must be `Clone` (e.g. buffer an HTTP body into `Bytes`).
+/// Inserts go to `main`: run a Daemon so they are committed and served.
+pub struct CacheLayer<Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, Resp>>,
+    key: KeyFn<Req, K>,
+    policy: PolicyFn<Req>,
+    cacheable: CacheableFn<Resp>,
+}
+
+impl<Req, K, Resp> Clone for CacheLayer<Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cache: Arc::clone(&self.cache),
+            key: Arc::clone(&self.key),
+            policy: Arc::clone(&self.policy),
+            cacheable: Arc::clone(&self.cacheable),
+        }
+    }
+}
+
+impl<Req, K, Resp> CacheLayer<Req, K, Resp>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    Resp: Clone + Send + Sync + 'static,
+{
+    /// Caches responses under `key(&request)` with the default `CachePolicy`.
+    pub fn new(
+        cache: Arc<DualCache<K, Resp>>,
+        key: impl Fn(&Req) -> Option<K> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            cache,
+            key: Arc::new(key),
+            policy: Arc::new(|_: &Req| CachePolicy::default()),
+            cacheable: Arc::new(|_: &Resp| true),
+        }
+    }
+
+    /// The same policy for every request.
+    pub fn default_policy(self, policy: CachePolicy) -> Self {
+        self.policy(move |_| policy)
+    }
+
+    /// Chooses the policy per request (e.g. by route).
+    pub fn policy(mut self, policy: impl Fn(&Req) -> CachePolicy + Send + Sync + 'static) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+
+    /// Only caches responses passing `cacheable` (e.g. success statuses).
+    pub fn cache_if(mut self, cacheable: impl Fn(&Resp) -> bool + Send + Sync + 'static) -> Self {
+        self.cacheable = Arc::new(cacheable);
+        self
+    }
+
+    pub fn cache(&self) -> &Arc<DualCache<K, Resp>> {
+        &self.cache
+    }
+}
+
+impl<Svc, Req, K, Resp> Layer<Svc> for CacheLayer<Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    type Service = CacheService<Svc, Req, K, Resp>;
+
+    fn layer(&self, inner: Svc) -> Self::Service {
+        CacheService {
+            inner,
+            shared: Arc::new(Shared {
+                cache: Arc::clone(&self.cache),
+                key: Arc::clone(&self.key),
+                policy: Arc::clone(&self.policy),
+                cacheable: Arc::clone(&self.cacheable),
+                revalidating: Mutex::new(HashSet::new()),
+            }),
+        }
+    }
+}
+
+/// The `Service` produced by `CacheLayer`.
+pub struct CacheService<Svc, Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    inner: Svc,
+    shared: Arc<Shared<Req, K, Resp>>,
+}
+
+impl<Svc: Clone, Req, K, Resp> Clone for CacheService<Svc, Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<Svc, Req, K, Resp> Service<Req> for CacheService<Svc, Req, K, Resp>
+where
+    Svc: Service<Req, Response = Resp> + Clone + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Error: Send + 'static,
+    Req: Send + 'static,
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    Resp: Clone + Send + Sync + 'static,
+{
+    type Response = Resp;
+    type Error = Svc::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Resp, Svc::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Req) -> Self::Future {
+        let Some(key) = (self.shared.key)(&request) else {
+            return Box::pin(self.inner.call(request));
+        };
+        let policy = (self.shared.policy)(&request);
+
+        match self.shared.cache.get_with_staleness(&key) {
+            Some((response, false)) => Box::pin(ready(Ok(response))),
+            Some((stale, true)) => {
+                if self.shared.revalidating.lock().insert(key.clone()) {
+                    let revalidation = self.ready_inner().call(request);
+                    let shared = Arc::clone(&self.shared);
+                    tokio::spawn(async move {
+                        if let Ok(response) = revalidation.await {
+                            shared.store(key.clone(), response, policy);
+                        }
+                        shared.revalidating.lock().remove(&key);
+                    });
+                }
+                Box::pin(ready(Ok(stale)))
+            }
+            None => {
+                let response = self.ready_inner().call(request);
+                let shared = Arc::clone(&self.shared);
+                Box::pin(async move {
+                    let response = response.await?;
+                    shared.store(key, response.clone(), policy);
+                    Ok(response)
+                })
+            }
+        }
+    }
+}
+
+impl<Svc: Clone, Req, K, Resp> CacheService<Svc, Req, K, Resp>
+where
+    K: Hash + Eq + Clone,
+{
+    // Helper: Takes the service `poll_ready` prepared, leaving a fresh clone
+    fn ready_inner(&mut self) -> Svc {
+        let clone = self.inner.clone();
+        std::mem::replace(&mut self.inner, clone)
+    }
+}
+
+impl<Req, K, Resp> Shared<Req, K, Resp>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    Resp: Clone + Send + Sync + 'static,
+{
+    fn store(&self, key: K, response: Resp, policy: CachePolicy) {
+        if (self.cacheable)(&response) {
+            self.cache.insert_with_stale_window(key, response, policy);
+        }
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    // Helper: A `get` that also reports whether the value is past its soft TTL
+    fn get_with_staleness<Q>(&self, key: &Q) -> Option<(V, bool)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let snapshot = self.mirror.load();
+        let now = snapshot.current_time();
+        self.read_in(&snapshot, key, |node| (node.value.clone(), node.is_stale(now)))
+    }
+
+    // Helper: Millisecond soft / hard deadlines from a policy; an existing
+    // key gets both deadlines reset
+    fn insert_with_stale_window(&self, key: K, value: V, policy: CachePolicy) {
+        let soft_ttl = u64::try_from(policy.ttl.as_millis()).unwrap_or(u64::MAX).max(1);
+        let window = u64::try_from(policy.stale_while_revalidate.as_millis()).unwrap_or(u64::MAX);
+        self.write(|cache| {
+            let now = cache.current_time();
+            let hard_deadline = now.saturating_add(soft_ttl).saturating_add(window);
+            if let Some(idx) = cache.position(&key) {
+                cache.move_deadline(idx, hard_deadline);
+                let node = &mut cache.arena[idx];
+                node.soft_ttl = soft_ttl;
+                node.soft_deadline = now.saturating_add(soft_ttl);
+                cache.update_value(&key, value);
+                return;
+            }
+            let mut node = cache.new_node(key, value, 0);
+            node.time_stamp = hard_deadline;
+            node.soft_ttl = soft_ttl;
+            node.soft_deadline = node.last_access.saturating_add(soft_ttl);
+            cache.gatsby_insert_node(node);
+        });
+    }
+}
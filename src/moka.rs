@@ -0,0 +1,254 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{CommitPolicy, DualCache, DualCacheBuilder, SignalPolicy};
+
+// -----------------------------------------------------------------------------
+// moka-Compatible Facade
+// -----------------------------------------------------------------------------
+
+// DualCache is always bounded; used when `max_capacity` is not set
+const DEFAULT_MAX_CAPACITY: u64 = 10_000;
+
+/// Drop-in for the common subset of `moka::sync::Cache`, backed by a
+/// `DualCache` (membrane eviction instead of TinyLFU). Migrating is mostly
+/// an import change: `Cache::builder().max_capacity(..).time_to_live(..).build()`.
+///
+/// Like moka there is no background thread: hits climb inline (the
+/// `SignalPolicy::Inline` mode) and every write commits, so `get` sees it
+/// immediately. `run_pending_tasks` recalculates the membrane and commits;
+/// call it periodically. `into_inner`/`inner` expose the `DualCache` for
+/// tuning once migrated.
+pub struct Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    inner: Arc<DualCache<K, V>>,
+    time_to_live: Option<Duration>,
+    time_to_idle: Option<Duration>,
+}
+
+impl<K, V> Clone for Cache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Another handle to the same cache, like moka.
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            time_to_live: self.time_to_live,
+            time_to_idle: self.time_to_idle,
+        }
+    }
+}
+
+/// Builder returned by `Cache::builder`.
+pub struct CacheBuilder<K, V> {
+    max_capacity: Option<u64>,
+    time_to_live: Option<Duration>,
+    time_to_idle: Option<Duration>,
+    _cache: PhantomData<fn(K, V)>,
+}
+
+impl<K, V> CacheBuilder<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Maximum number of entries (10,000 when unset; the cache is always bounded).
+    pub fn max_capacity(mut self, max_capacity: u64) -> Self {
+        self.max_capacity = Some(max_capacity);
+        self
+    }
+
+    /// Accepted for compatibility; the index is sized from `max_capacity`.
+    pub fn initial_capacity(self, _initial_capacity: usize) -> Self {
+        self
+    }
+
+    /// Entries expire this long after they were inserted (or replaced).
+    pub fn time_to_live(mut self, ttl: Duration) -> Self {
+        self.time_to_live = Some(ttl);
+        self
+    }
+
+    /// Entries also expire after this long without a read.
+    pub fn time_to_idle(mut self, tti: Duration) -> Self {
+        self.time_to_idle = Some(tti);
+        self
+    }
+
+    pub fn build(self) -> Cache<K, V> {
+        let capacity = usize::try_from(self.max_capacity.unwrap_or(DEFAULT_MAX_CAPACITY)).unwrap_or(usize::MAX);
+        let (inner, _rx) = DualCacheBuilder::new(capacity)
+            .signal_policy(SignalPolicy::Inline)
+            .commit_policy(CommitPolicy::EveryNWrites(1))
+            .build();
+        Cache {
+            inner,
+            time_to_live: self.time_to_live,
+            time_to_idle: self.time_to_idle,
+        }
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// A cache holding up to `max_capacity` entries, without expiration.
+    pub fn new(max_capacity: u64) -> Self {
+        Self::builder().max_capacity(max_capacity).build()
+    }
+
+    pub fn builder() -> CacheBuilder<K, V> {
+        CacheBuilder {
+            max_capacity: None,
+            time_to_live: None,
+            time_to_idle: None,
+            _cache: PhantomData,
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.contains_key(key)
+    }
+
+    /// Inserts or replaces `key`, restarting its expiration.
+    pub fn insert(&self, key: K, value: V) {
+        let (ttl, tti) = (self.time_to_live, self.time_to_idle);
+        self.inner.write(|cache| cache.insert_expiring(key, value, ttl, tti));
+    }
+
+    /// Returns the cached value or inserts the one `init` computes. Concurrent
+    /// callers for a missing key run `init` once (under the main lock).
+    pub fn get_with(&self, key: K, init: impl FnOnce() -> V) -> V {
+        match self.try_get_with(key, || Ok::<_, std::convert::Infallible>(init())) {
+            Ok(value) => value,
+            Err(never) => match *never {},
+        }
+    }
+
+    /// Like `get_with`; `None` from `init` caches nothing.
+    pub fn optionally_get_with(&self, key: K, init: impl FnOnce() -> Option<V>) -> Option<V> {
+        self.try_get_with(key, || init().ok_or(())).ok()
+    }
+
+    /// Like `get_with`; an error from `init` caches nothing and is returned
+    /// in an `Arc`, as moka does.
+    pub fn try_get_with<E>(&self, key: K, init: impl FnOnce() -> Result<V, E>) -> Result<V, Arc<E>> {
+        if let Some(value) = self.inner.get(&key) {
+            return Ok(value);
+        }
+        let (ttl, tti) = (self.time_to_live, self.time_to_idle);
+        self.inner.write(|cache| {
+            // Re-check under the lock: another caller may have inserted meanwhile
+            let now = cache.current_time();
+            if let Some(idx) = cache.position(&key)
+                && !cache.arena[idx].is_expired(now)
+            {
+                return Ok(cache.arena[idx].value.clone());
+            }
+            let value = init().map_err(Arc::new)?;
+            cache.insert_expiring(key, value.clone(), ttl, tti);
+            Ok(value)
+        })
+    }
+
+    pub fn invalidate<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.delete(key);
+    }
+
+    /// Invalidates `key`, returning its value if it was live.
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.write(|cache| {
+            let now = cache.current_time();
+            cache
+                .double_swap_delete(key)
+                .filter(|node| !node.is_expired(now))
+                .map(|node| node.value)
+        })
+    }
+
+    pub fn invalidate_all(&self) {
+        self.inner.retain(|_, _| false);
+    }
+
+    /// Housekeeping moka defers: recalculates the membrane and commits.
+    pub fn run_pending_tasks(&self) {
+        self.inner.maintenance();
+        self.inner.commit();
+    }
+
+    /// Live entries as of the last commit.
+    pub fn entry_count(&self) -> u64 {
+        self.inner.len() as u64
+    }
+
+    pub fn inner(&self) -> &Arc<DualCache<K, V>> {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> Arc<DualCache<K, V>> {
+        self.inner
+    }
+}
+
+impl<K, V, S> crate::Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Inserts or replaces `key` with fresh moka-style deadlines
+    // (no TTL never expires; TTI is measured from the last applied read)
+    fn insert_expiring(&mut self, key: K, value: V, ttl: Option<Duration>, tti: Option<Duration>) {
+        let now = self.current_time();
+        let deadline = ttl.map_or(u64::MAX, |ttl| now.saturating_add(duration_millis(ttl)));
+        let tti = tti.map_or(0, |tti| duration_millis(tti).max(1));
+        if let Some(idx) = self.position(&key) {
+            self.move_deadline(idx, deadline);
+            let node = &mut self.arena[idx];
+            node.tti = tti;
+            node.last_access = now;
+            if tti > 0 {
+                self.has_idle_entries = true;
+            }
+            self.update_value(&key, value);
+            return;
+        }
+        let mut node = self.new_node(key, value, 0);
+        node.time_stamp = deadline;
+        node.tti = tti;
+        self.gatsby_insert_node(node);
+    }
+}
+
+// Helper: Whole milliseconds of `duration`, saturating
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
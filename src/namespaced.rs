@@ -0,0 +1,212 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use crossbeam::channel::Receiver;
+use parking_lot::RwLock;
+
+use crate::{CacheStats, DualCache, DualCacheBuilder, Signal};
+
+// -----------------------------------------------------------------------------
+// Namespaced DualCache (Per-Tenant Quotas)
+// -----------------------------------------------------------------------------
+
+/// Why a `NamespacedDualCache` call was refused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NamespaceError {
+    /// No namespace by that name.
+    Unknown(String),
+    /// `add_namespace` with a name already in use.
+    Exists(String),
+    /// The quota does not fit the unassigned part of the budget.
+    OverBudget { requested: usize, available: usize },
+}
+
+impl fmt::Display for NamespaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "unknown namespace {name:?}"),
+            Self::Exists(name) => write!(f, "namespace {name:?} already exists"),
+            Self::OverBudget { requested, available } => {
+                write!(f, "quota of {requested} entries exceeds the {available} left in the budget")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NamespaceError {}
+
+/// One entry budget shared by named namespaces (e.g. tenants). Each
+/// namespace is its own `DualCache` whose capacity is its quota, so a busy
+/// tenant only ever evicts its own entries. Quotas are carved out of the
+/// budget; `put` uses the namespace's default TTL.
+///
+/// `add_namespace` returns the namespace's signal receiver: spawn a Daemon
+/// on `namespace(name)` with it, as with a plain `DualCache`.
+pub struct NamespacedDualCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    budget: usize,
+    builder: DualCacheBuilder,
+    namespaces: RwLock<HashMap<String, Arc<DualCache<K, V>>>>,
+}
+
+impl<K, V> NamespacedDualCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// A budget of `budget` entries with no namespaces yet.
+    pub fn new(budget: usize) -> Self {
+        Self::with_builder(budget, DualCacheBuilder::new(budget))
+    }
+
+    /// Namespaces are built from `builder`; its capacity and default TTL are
+    /// replaced by each namespace's quota and TTL.
+    pub fn with_builder(budget: usize, builder: DualCacheBuilder) -> Self {
+        Self {
+            budget,
+            builder,
+            namespaces: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Creates namespace `name` holding up to `quota` entries, with
+    /// `default_ttl_secs` for `put`.
+    pub fn add_namespace(
+        &self,
+        name: impl Into<String>,
+        quota: usize,
+        default_ttl_secs: u64,
+    ) -> Result<Receiver<Signal<K>>, NamespaceError> {
+        let name = name.into();
+        let mut namespaces = self.namespaces.write();
+        if namespaces.contains_key(&name) {
+            return Err(NamespaceError::Exists(name));
+        }
+        let available = self.budget - assigned(&namespaces);
+        if quota > available {
+            return Err(NamespaceError::OverBudget { requested: quota, available });
+        }
+
+        let mut builder = self.builder.clone();
+        builder.capacity = quota;
+        builder.default_ttl = default_ttl_secs;
+        let (cache, rx) = builder.build();
+        namespaces.insert(name, cache);
+        Ok(rx)
+    }
+
+    /// Drops namespace `name` and everything in it, returning its quota to the budget.
+    pub fn remove_namespace(&self, name: &str) -> Result<(), NamespaceError> {
+        self.namespaces
+            .write()
+            .remove(name)
+            .map(drop)
+            .ok_or_else(|| NamespaceError::Unknown(name.into()))
+    }
+
+    /// Moves namespace `name` to a new quota (evicting its tail if it shrinks).
+    pub fn set_quota(&self, name: &str, quota: usize) -> Result<(), NamespaceError> {
+        let namespaces = self.namespaces.write();
+        let cache = namespaces.get(name).ok_or_else(|| NamespaceError::Unknown(name.into()))?;
+        let available = self.budget - assigned(&namespaces) + cache.capacity();
+        if quota > available {
+            return Err(NamespaceError::OverBudget { requested: quota, available });
+        }
+        cache.resize(quota);
+        Ok(())
+    }
+
+    /// The cache backing namespace `name` (for daemons, stats, tuning).
+    pub fn namespace(&self, name: &str) -> Option<Arc<DualCache<K, V>>> {
+        self.namespaces.read().get(name).cloned()
+    }
+
+    pub fn namespaces(&self) -> Vec<String> {
+        self.namespaces.read().keys().cloned().collect()
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Budget not assigned to any namespace.
+    pub fn unassigned(&self) -> usize {
+        self.budget - assigned(&self.namespaces.read())
+    }
+
+    pub fn get<Q>(&self, name: &str, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.namespaces.read().get(name)?.get(key)
+    }
+
+    /// Inserts with the namespace's default TTL.
+    pub fn put(&self, name: &str, key: K, value: V) -> Result<(), NamespaceError> {
+        self.with_namespace(name, |cache| cache.put(key, value))
+    }
+
+    pub fn insert(&self, name: &str, key: K, value: V, ttl_secs: u64) -> Result<(), NamespaceError> {
+        self.with_namespace(name, |cache| cache.insert(key, value, ttl_secs))
+    }
+
+    pub fn delete<Q>(&self, name: &str, key: &Q) -> Result<(), NamespaceError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.with_namespace(name, |cache| cache.delete(key))
+    }
+
+    /// Deletes every entry of namespace `name`; other namespaces are untouched.
+    /// Returns the number removed.
+    pub fn clear_namespace(&self, name: &str) -> Result<usize, NamespaceError> {
+        self.with_namespace(name, |cache| cache.clear())
+    }
+
+    pub fn maintenance(&self) {
+        self.namespaces.read().values().for_each(|cache| cache.maintenance());
+    }
+
+    /// Commits every namespace, one after another.
+    pub fn commit(&self) {
+        self.namespaces.read().values().for_each(|cache| {
+            cache.commit();
+        });
+    }
+
+    pub fn stats(&self, name: &str) -> Option<CacheStats> {
+        self.namespaces.read().get(name).map(|cache| cache.stats())
+    }
+
+    /// Sum of all namespace statistics.
+    pub fn total_stats(&self) -> CacheStats {
+        self.namespaces
+            .read()
+            .values()
+            .map(|cache| cache.stats())
+            .fold(CacheStats::default(), CacheStats::merge)
+    }
+
+    // Helper: Runs `op` on namespace `name`
+    fn with_namespace<R>(&self, name: &str, op: impl FnOnce(&DualCache<K, V>) -> R) -> Result<R, NamespaceError> {
+        let namespaces = self.namespaces.read();
+        let cache = namespaces.get(name).ok_or_else(|| NamespaceError::Unknown(name.into()))?;
+        Ok(op(cache))
+    }
+}
+
+// Helper: Budget held by existing namespaces
+fn assigned<K, V>(namespaces: &HashMap<String, Arc<DualCache<K, V>>>) -> usize
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    namespaces.values().map(|cache| cache.capacity()).sum()
+}
@@ -0,0 +1,323 @@
+use std::fs::{self, File, OpenOptions};
+use std::hash::{BuildHasher, Hash};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crossbeam::channel::Receiver;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{Cache, DualCache, DualCacheBuilder, Node, Signal, SnapshotError};
+
+// -----------------------------------------------------------------------------
+// Write-Ahead Operation Log (feature = "disk")
+// -----------------------------------------------------------------------------
+
+// One line of the log, written from borrowed data
+#[derive(Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum LogEntry<'a, K, V> {
+    Insert { at: u64, node: &'a Node<K, V> },
+    Update { at: u64, key: &'a K, value: &'a V },
+    Deadline { at: u64, key: &'a K, deadline: u64 },
+    Delete { at: u64, key: &'a K },
+}
+
+// The same line, read back (replay does not need the timestamp)
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum LoggedOp<K, V> {
+    Insert { node: Node<K, V> },
+    Update { key: K, value: V },
+    Deadline { key: K, deadline: u64 },
+    Delete { key: K },
+}
+
+// Write-path hook stored in `Cache`, so the cache itself needs no serde bounds
+pub(crate) trait WriteLog<K, V>: Send + Sync {
+    fn log_insert(&self, at: u64, node: &Node<K, V>);
+    fn log_update(&self, at: u64, key: &K, value: &V);
+    fn log_deadline(&self, at: u64, key: &K, deadline: u64);
+    fn log_delete(&self, at: u64, key: &K);
+    // Current end of the log; taken under the main lock it separates the
+    // records a snapshot contains from later ones
+    fn mark(&self) -> Result<u64, SnapshotError>;
+    // Drops the records before `mark` (covered by a durable checkpoint);
+    // returns the bytes removed
+    fn discard_before(&self, mark: u64) -> Result<u64, SnapshotError>;
+}
+
+/// Append-only log of placements, value updates, deadline changes and
+/// deletes (one JSON record per line, timestamped with the cache clock),
+/// appended by the write path under the main lock. `DualCache::recover` replays it after a crash.
+///
+/// Climbs, evictions and expirations are not logged: replay re-derives
+/// them from the logged inserts and deadlines. In-place edits through
+/// `OccupiedEntry::get_mut` are logged as an update once the entry is dropped.
+///
+/// The write path cannot return I/O errors: a failed append is counted in
+/// `failed_writes` and the record is lost. Records reach the OS on every
+/// append; call `sync` for durability against power loss.
+pub struct OpLog<K, V> {
+    path: PathBuf,
+    file: Mutex<File>,
+    failed_writes: AtomicU64,
+    _entries: std::marker::PhantomData<fn(K, V)>,
+}
+
+impl<K, V> OpLog<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    /// Opens (or creates) the log at `path` for appending.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().append(true).create(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            failed_writes: AtomicU64::new(0),
+            _entries: std::marker::PhantomData,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends that failed (the cache kept going without them).
+    pub fn failed_writes(&self) -> u64 {
+        self.failed_writes.load(Ordering::Relaxed)
+    }
+
+    /// Flushes appended records to stable storage (`fsync`).
+    pub fn sync(&self) -> Result<(), SnapshotError> {
+        self.file.lock().sync_data()?;
+        Ok(())
+    }
+
+    // Helper: Writes one record as a single line (one `write` call, so a
+    // crash leaves at most a torn last line)
+    fn append(&self, entry: &LogEntry<'_, K, V>) {
+        let written = serde_json::to_vec(entry).map_err(SnapshotError::from).and_then(|mut line| {
+            line.push(b'\n');
+            self.file.lock().write_all(&line).map_err(SnapshotError::from)
+        });
+        if written.is_err() {
+            self.failed_writes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    // Helper: Reads every complete record; a torn final line (crash mid-append)
+    // is cut off so later appends start on a fresh line
+    fn read_ops(path: &Path) -> Result<Vec<LoggedOp<K, V>>, SnapshotError> {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut reader = BufReader::new(file);
+        let mut ops = Vec::new();
+        let mut line = String::new();
+        let mut complete = 0;
+        while reader.read_line(&mut line)? > 0 {
+            if !line.ends_with('\n') {
+                OpenOptions::new().write(true).open(path)?.set_len(complete)?;
+                break;
+            }
+            ops.push(serde_json::from_str(line.trim_end())?);
+            complete += line.len() as u64;
+            line.clear();
+        }
+        Ok(ops)
+    }
+}
+
+impl<K, V> WriteLog<K, V> for OpLog<K, V>
+where
+    K: Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    fn log_insert(&self, at: u64, node: &Node<K, V>) {
+        self.append(&LogEntry::Insert { at, node });
+    }
+
+    fn log_update(&self, at: u64, key: &K, value: &V) {
+        self.append(&LogEntry::Update { at, key, value });
+    }
+
+    fn log_deadline(&self, at: u64, key: &K, deadline: u64) {
+        self.append(&LogEntry::Deadline { at, key, deadline });
+    }
+
+    fn log_delete(&self, at: u64, key: &K) {
+        self.append(&LogEntry::Delete { at, key });
+    }
+
+    fn mark(&self) -> Result<u64, SnapshotError> {
+        Ok(self.file.lock().metadata()?.len())
+    }
+
+    fn discard_before(&self, mark: u64) -> Result<u64, SnapshotError> {
+        // Appends wait on the file lock while the tail is copied
+        let mut file = self.file.lock();
+        let mut tail = Vec::new();
+        let mut reader = File::open(&self.path)?;
+        reader.seek(SeekFrom::Start(mark))?;
+        reader.read_to_end(&mut tail)?;
+
+        let tmp_path = self.path.with_extension("truncate");
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(&tail)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        *file = OpenOptions::new().append(true).open(&self.path)?;
+        Ok(mark)
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    S: BuildHasher + Clone + Default + Send + Sync + 'static,
+{
+    /// Appends every following insert, update and delete to `log`
+    /// (replacing any previous log).
+    pub fn set_op_log(&self, log: Arc<OpLog<K, V>>) {
+        self.main.lock().oplog = Some(log);
+    }
+
+    /// Rebuilds a cache by replaying the log at `log_path` (a missing file
+    /// gives an empty cache), then keeps appending to it. Entries whose
+    /// deadline passed are skipped. The recovered state is committed before
+    /// returning; ranks reflect insertion order, not the original hit history.
+    pub fn recover(
+        log_path: impl AsRef<Path>,
+        capacity: usize,
+    ) -> Result<(Arc<Self>, Receiver<Signal<K>>), SnapshotError> {
+        let (cache, rx) = DualCacheBuilder::new(capacity).build_with_hasher(S::default());
+        cache.replay_log(log_path.as_ref())?;
+        Ok((cache, rx))
+    }
+
+    // Helper: Replays the log on top of the current state, attaches it and commits
+    pub(crate) fn replay_log(&self, log_path: &Path) -> Result<(), SnapshotError> {
+        let ops = OpLog::<K, V>::read_ops(log_path)?;
+        let log = Arc::new(OpLog::open(log_path)?);
+        {
+            let mut main = self.main.lock();
+            main.replay(ops);
+            main.oplog = Some(log);
+        }
+        self.commit();
+        Ok(())
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Applies logged operations in order (nothing is re-logged)
+    fn replay(&mut self, ops: Vec<LoggedOp<K, V>>) {
+        self.dirty = true;
+        let now = self.current_time();
+        for op in ops {
+            match op {
+                LoggedOp::Insert { node } => {
+                    if node.is_expired(now) {
+                        continue;
+                    }
+                    // Same accounting as `insert_pinned` for a new key
+                    let key = (node.pinned && self.position(&node.key).is_none()).then(|| node.key.clone());
+                    self.gatsby_insert_node(node);
+                    if let Some(key) = key
+                        && self.position(&key).is_some_and(|idx| self.arena[idx].pinned)
+                    {
+                        self.pinned_count += 1;
+                    }
+                }
+                LoggedOp::Update { key, value } => self.update_value(&key, value),
+                LoggedOp::Deadline { key, deadline } => {
+                    if let Some(idx) = self.position(&key) {
+                        self.move_deadline(idx, deadline);
+                    }
+                }
+                LoggedOp::Delete { key } => {
+                    self.double_swap_delete(&key);
+                }
+            }
+        }
+    }
+
+    // Helper: Logs a placed node
+    pub(crate) fn log_insert(&self, node: &Node<K, V>) {
+        if let Some(log) = &self.oplog {
+            log.log_insert(self.current_time(), node);
+        }
+    }
+
+    // Helper: Logs the current value of the node at `idx`
+    pub(crate) fn log_update(&self, idx: usize) {
+        if let Some(log) = &self.oplog {
+            let node = &self.arena[idx];
+            log.log_update(self.current_time(), &node.key, &node.value);
+        }
+    }
+
+    // Helper: Logs the current deadline of the node at `idx`
+    pub(crate) fn log_deadline(&self, idx: usize) {
+        if let Some(log) = &self.oplog {
+            let node = &self.arena[idx];
+            log.log_deadline(self.current_time(), &node.key, node.time_stamp);
+        }
+    }
+
+    // Helper: Logs a deleted key
+    pub(crate) fn log_delete(&self, key: &K) {
+        if let Some(log) = &self.oplog {
+            log.log_delete(self.current_time(), key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recover_cuts_torn_tail() {
+        let path = std::env::temp_dir().join(format!("dualcache-oplog-torn-{}.log", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let (cache, _rx) = DualCache::<u32, u32>::recover(&path, 16).unwrap();
+        cache.insert(1, 10, 60);
+        cache.insert(2, 20, 60);
+        drop(cache);
+        let complete = fs::metadata(&path).unwrap().len();
+
+        // Crash mid-append
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"op\":\"ins").unwrap();
+
+        let (cache, _rx) = DualCache::<u32, u32>::recover(&path, 16).unwrap();
+        assert_eq!((cache.get(&1), cache.get(&2)), (Some(10), Some(20)));
+        assert_eq!(fs::metadata(&path).unwrap().len(), complete);
+
+        // Later appends start on a fresh line
+        cache.insert(3, 30, 60);
+        drop(cache);
+        let (cache, _rx) = DualCache::<u32, u32>::recover(&path, 16).unwrap();
+        assert_eq!(cache.get(&3), Some(30));
+        assert_eq!(cache.len(), 3);
+
+        drop(cache);
+        fs::remove_file(&path).unwrap();
+    }
+}
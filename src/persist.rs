@@ -0,0 +1,144 @@
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+
+use crossbeam::channel::Receiver;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::{Cache, DualCache, DualCacheBuilder, Node, Signal, secs_to_millis};
+
+// -----------------------------------------------------------------------------
+// Snapshot Persistence (feature = "serde")
+// -----------------------------------------------------------------------------
+
+/// On-disk form of the main cache: live nodes in rank order plus membrane state.
+/// Dangling garbage (expired / truncated slots) is not persisted.
+#[derive(Serialize, Deserialize)]
+struct PersistedCache<K, V> {
+    nodes: Vec<Node<K, V>>,
+    // Kept for older readers; restore recomputes it from `nodes`
+    counter_sum: u64,
+    evict_point: usize,
+    // Snapshots written before millisecond deadlines lack this and store seconds
+    #[serde(default)]
+    millis: bool,
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    Format(serde_json::Error),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "snapshot I/O error: {err}"),
+            Self::Format(err) => write!(f, "snapshot format error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Format(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SnapshotError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    V: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+    S: BuildHasher + Clone + Default + Send + Sync + 'static,
+{
+    /// Writes the authoritative (main) state, including uncommitted writes.
+    /// The lock is held only for a structural clone, not during serialization.
+    pub fn save_snapshot<W: Write>(&self, writer: W) -> Result<(), SnapshotError> {
+        let cache = self.main.lock().clone();
+        cache.write_snapshot(writer)
+    }
+
+    /// Rebuilds a cache from `save_snapshot` output, preserving rank order,
+    /// counters, timestamps, and `evict_point`. Nodes beyond `capacity` are dropped.
+    /// The restored state is committed before returning.
+    pub fn load_snapshot<R: Read>(
+        reader: R,
+        capacity: usize,
+    ) -> Result<(Arc<Self>, Receiver<Signal<K>>), SnapshotError> {
+        let mut persisted: PersistedCache<K, V> = serde_json::from_reader(reader)?;
+        if !persisted.millis {
+            persisted.nodes.iter_mut().for_each(Node::upgrade_to_millis);
+        }
+        let (cache, rx) = DualCacheBuilder::new(capacity).build_with_hasher(S::default());
+
+        {
+            let mut main = cache.main.lock();
+            main.restore(persisted.nodes);
+            main.evict_point = persisted.evict_point.min(main.capacity);
+        }
+        cache.commit();
+
+        Ok((cache, rx))
+    }
+}
+
+impl<K, V> Node<K, V> {
+    // Helper: Rescales a seconds-based node from an older snapshot
+    fn upgrade_to_millis(&mut self) {
+        for field in [
+            &mut self.time_stamp,
+            &mut self.tti,
+            &mut self.last_access,
+            &mut self.soft_ttl,
+            &mut self.soft_deadline,
+            &mut self.inserted_at,
+        ] {
+            *field = secs_to_millis(*field);
+        }
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Serializes this (detached) cache in the `save_snapshot` format
+    pub(crate) fn write_snapshot<W: Write>(&self, writer: W) -> Result<(), SnapshotError>
+    where
+        K: Serialize,
+        V: Serialize,
+    {
+        let persisted = PersistedCache {
+            nodes: self.live_slots().map(|idx| self.counted_clone(idx)).collect(),
+            counter_sum: self.counter_sum,
+            evict_point: self.evict_point,
+            millis: true,
+        };
+        serde_json::to_writer(writer, &persisted)?;
+        Ok(())
+    }
+
+    // Helper: Slots still reachable through the index (skips dangling garbage)
+    fn live_slots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.arena.len()).filter(|&i| self.position(&self.arena[i].key).is_some())
+    }
+}
@@ -0,0 +1,90 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Pinned Entries (Immune to Cliff Edge and TTL)
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Inserts (or replaces the value of) a pinned entry. Pinned entries never
+    /// expire and survive truncation; they still count towards capacity.
+    /// The builder's default TTL applies again once unpinned.
+    pub fn insert_pinned(&self, key: K, value: V) {
+        self.write(|cache| {
+            if let Some(idx) = cache.position(&key) {
+                cache.set_pinned(idx, true);
+                cache.update_value(&key, value);
+                return;
+            }
+            let mut node = cache.new_node(key.clone(), value, self.default_ttl);
+            node.pinned = true;
+            cache.gatsby_insert_node(node);
+            // Counted only once placed: bans and the admission filter can reject it
+            if cache.position(&key).is_some_and(|idx| cache.arena[idx].pinned) {
+                cache.pinned_count += 1;
+            }
+        });
+    }
+
+    /// Pins a live entry. Returns false if the key is absent.
+    pub fn pin<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.set_pinned(key, true)
+    }
+
+    /// Unpins an entry. Its original TTL deadline applies again, so an entry
+    /// pinned past its deadline expires on the next check.
+    /// Returns false if the key is absent.
+    pub fn unpin<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.set_pinned(key, false)
+    }
+
+    fn set_pinned<Q>(&self, key: &Q, pinned: bool) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| match cache.position(key) {
+            Some(idx) => {
+                cache.set_pinned(idx, pinned);
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    fn set_pinned(&mut self, idx: usize, pinned: bool) {
+        if self.arena[idx].pinned == pinned {
+            return;
+        }
+        self.arena[idx].pinned = pinned;
+        if pinned {
+            self.pinned_count += 1;
+        } else {
+            self.pinned_count -= 1;
+            // Pinned nodes are kept off the expiry wheel
+            self.schedule_expiry(idx);
+        }
+    }
+}
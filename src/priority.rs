@@ -0,0 +1,102 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Priority Classes (Eviction Order at the Cliff Edge)
+// -----------------------------------------------------------------------------
+
+/// Eviction class of an entry. When the cliff edge cuts the arena, victims
+/// of a higher class trade places with lower-class nodes just above the cut
+/// (the at-risk zone, as deep as the cut), so lower classes go first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Priority {
+    /// Evicted ahead of everything else near the edge.
+    BestEffort,
+    #[default]
+    Normal,
+    /// Soft-pinned: survives truncation while a lower-class node in the
+    /// at-risk zone can go instead. Unlike pinned entries it still expires.
+    Critical,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Inserts with an eviction class. An existing key gets the new value and
+    /// class but keeps its TTL.
+    pub fn insert_with_priority(&self, key: K, value: V, ttl_secs: u64, priority: Priority) {
+        self.write(|cache| {
+            if let Some(idx) = cache.position(&key) {
+                cache.set_priority(idx, priority);
+                cache.update_value(&key, value);
+                return;
+            }
+            let mut node = cache.new_node(key, value, ttl_secs);
+            node.priority = priority;
+            cache.gatsby_insert_node(node);
+        });
+    }
+
+    /// Changes the class of a live entry. Returns false if the key is absent.
+    pub fn set_priority<Q>(&self, key: &Q, priority: Priority) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| match cache.position(key) {
+            Some(idx) => {
+                cache.set_priority(idx, priority);
+                true
+            }
+            None => false,
+        })
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    fn set_priority(&mut self, idx: usize, priority: Priority) {
+        self.has_priorities |= priority != Priority::Normal;
+        self.arena[idx].priority = priority;
+    }
+
+    // Helper: Before cutting at `start`, swaps live victims of each class with
+    // lower-class (or dead) unpinned nodes of the at-risk zone above the cut,
+    // Critical first. Only runs once a non-Normal class was ever assigned.
+    pub(crate) fn prioritize_victims(&mut self, start: usize) {
+        let len = self.arena.len();
+        if !self.has_priorities || start == 0 || start >= len {
+            return;
+        }
+        let zone_start = start.saturating_sub(len - start);
+
+        for class in [Priority::Critical, Priority::Normal] {
+            // Next candidate slot of the at-risk zone, walking up from the cut
+            let mut partner = start;
+            'victims: for idx in start..len {
+                if !self.is_live(idx) || self.arena[idx].priority != class {
+                    continue;
+                }
+                while partner > zone_start {
+                    partner -= 1;
+                    let node = &self.arena[partner];
+                    if !self.is_live(partner) || (node.priority < class && !node.pinned) {
+                        self.swap_nodes(idx, partner);
+                        continue 'victims;
+                    }
+                }
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use crossbeam::channel::Receiver;
+use parking_lot::Mutex;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyString};
+
+use crate::{DaemonConfig, DaemonHandle, DualCache, DualCacheBuilder, Signal};
+
+// -----------------------------------------------------------------------------
+// Python Bindings (feature = "python")
+// -----------------------------------------------------------------------------
+
+// Build the extension with maturin, enabling `python` plus
+// `pyo3/extension-module`; the module is importable as `dualcache`.
+
+/// A Python cache key: `str` and `bytes` are distinct keys.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum PyKey {
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+impl<'py> FromPyObject<'py> for PyKey {
+    fn extract_bound(key: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(key) = key.downcast::<PyString>() {
+            return Ok(Self::Str(key.to_str()?.to_owned()));
+        }
+        if let Ok(key) = key.downcast::<PyBytes>() {
+            return Ok(Self::Bytes(key.as_bytes().to_vec()));
+        }
+        Err(PyTypeError::new_err("DualCache keys must be str or bytes"))
+    }
+}
+
+// Values stay Python objects; the `Arc` lets the cache clone them without the GIL
+type PyValue = Arc<PyObject>;
+
+/// `DualCache` over `str` / `bytes` keys and arbitrary Python values, with
+/// the same eviction behavior as the Rust cache. Reads hit the mirror, so
+/// writes show up after `commit()` (or the daemon's next commit).
+#[pyclass(name = "DualCache", module = "dualcache")]
+pub struct PyDualCache {
+    cache: Arc<DualCache<PyKey, PyValue>>,
+    // Held until `start_daemon` hands it to the daemon thread
+    rx: Mutex<Option<Receiver<Signal<PyKey>>>>,
+    daemon: Mutex<Option<DaemonHandle>>,
+}
+
+#[pymethods]
+impl PyDualCache {
+    #[new]
+    #[pyo3(signature = (capacity, default_ttl = 3_600))]
+    fn new(capacity: usize, default_ttl: u64) -> Self {
+        let (cache, rx) = DualCacheBuilder::new(capacity).default_ttl(default_ttl).build();
+        Self {
+            cache,
+            rx: Mutex::new(Some(rx)),
+            daemon: Mutex::new(None),
+        }
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: PyKey, default: Option<PyObject>) -> Option<PyObject> {
+        match self.cache.get(&key) {
+            Some(value) => Some(value.clone_ref(py)),
+            None => default,
+        }
+    }
+
+    /// `ttl_secs` defaults to the `default_ttl` given at construction.
+    #[pyo3(signature = (key, value, ttl_secs = None))]
+    fn insert(&self, key: PyKey, value: PyObject, ttl_secs: Option<u64>) {
+        match ttl_secs {
+            Some(ttl_secs) => self.cache.insert(key, Arc::new(value), ttl_secs),
+            None => self.cache.put(key, Arc::new(value)),
+        }
+    }
+
+    fn delete(&self, key: PyKey) {
+        self.cache.delete(&key);
+    }
+
+    /// Publishes pending writes; returns the generation now visible.
+    fn commit(&self, py: Python<'_>) -> u64 {
+        py.allow_threads(|| self.cache.commit())
+    }
+
+    fn maintenance(&self, py: Python<'_>) {
+        py.allow_threads(|| self.cache.maintenance());
+    }
+
+    fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = self.cache.stats();
+        let dict = PyDict::new(py);
+        dict.set_item("hits", stats.hits)?;
+        dict.set_item("misses", stats.misses)?;
+        dict.set_item("hit_ratio", stats.hit_ratio())?;
+        dict.set_item("insertions", stats.insertions)?;
+        dict.set_item("evictions", stats.evictions)?;
+        dict.set_item("expirations", stats.expirations)?;
+        dict.set_item("rejections", stats.rejections)?;
+        dict.set_item("dropped_signals", stats.dropped_signals)?;
+        dict.set_item("occupancy", stats.occupancy)?;
+        dict.set_item("capacity", stats.capacity)?;
+        Ok(dict)
+    }
+
+    /// Runs the built-in daemon on a background thread (default intervals).
+    /// Returns False if it was already started.
+    fn start_daemon(&self) -> bool {
+        let Some(rx) = self.rx.lock().take() else { return false };
+        *self.daemon.lock() = Some(self.cache.spawn_daemon(rx, DaemonConfig::default()));
+        true
+    }
+
+    /// Stops the daemon after it applied queued signals and committed.
+    fn stop_daemon(&self, py: Python<'_>) {
+        if let Some(daemon) = self.daemon.lock().take() {
+            py.allow_threads(|| daemon.close());
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.cache.len()
+    }
+
+    fn __contains__(&self, key: PyKey) -> bool {
+        self.cache.contains_key(&key)
+    }
+}
+
+#[pymodule]
+fn dualcache(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyDualCache>()
+}
@@ -0,0 +1,169 @@
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{Cache, DualCache, Ttl, secs_to_millis};
+
+// -----------------------------------------------------------------------------
+// Stale-While-Revalidate (Soft / Hard TTL)
+// -----------------------------------------------------------------------------
+
+/// Reloads a stale entry. Runs on the Daemon without the main lock held.
+/// Returning `None` keeps serving the stale value until its hard TTL.
+pub type Refresher<K, V> = Arc<dyn Fn(&K) -> Option<V> + Send + Sync>;
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Inserts with two deadlines: after `soft_ttl_secs` reads still hit but
+    /// send `Signal::Refresh`; after `hard_ttl_secs` the entry is a miss.
+    /// Like `insert`, an existing key only has its value replaced.
+    pub fn insert_with_soft_ttl(&self, key: K, value: V, soft_ttl_secs: u64, hard_ttl_secs: u64) {
+        self.write(|cache| {
+            let mut node = cache.new_node(key, value, hard_ttl_secs);
+            node.soft_ttl = secs_to_millis(soft_ttl_secs);
+            node.soft_deadline = node.last_access.saturating_add(node.soft_ttl);
+            cache.gatsby_insert_node(node);
+        });
+    }
+
+    /// Installs the loader used for `Signal::Refresh`. Without one, refresh
+    /// signals only count as reads.
+    pub fn set_refresher(&self, refresher: Refresher<K, V>) {
+        self.main.lock().refresher = Some(refresher);
+    }
+
+    // Helper: Applies a refresh signal; the loader runs outside the main lock
+    pub(crate) fn refresh(&self, key: K) {
+        let refresher = {
+            let mut guard = self.main.lock();
+            guard.viscous_climb(key.clone());
+            let now = guard.current_time();
+            match guard.position(&key) {
+                // An earlier signal may already have refreshed it
+                Some(idx) if guard.arena[idx].is_stale(now) => guard.refresher.clone(),
+                _ => None,
+            }
+        };
+
+        let Some(refresher) = refresher else { return };
+        if let Some(value) = refresher(&key) {
+            self.write(|cache| cache.apply_refresh(&key, value));
+        }
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Swaps in the reloaded value and restarts both deadlines
+    fn apply_refresh(&mut self, key: &K, value: V) {
+        let now = self.current_time();
+        let Some(idx) = self.position(key) else { return };
+        if !self.arena[idx].is_stale(now) {
+            return;
+        }
+
+        let node = &self.arena[idx];
+        let inserted_at = node.soft_deadline.saturating_sub(node.soft_ttl);
+        let hard_ttl = node.time_stamp.saturating_sub(inserted_at);
+        let soft_ttl = node.soft_ttl;
+
+        self.update_value(key, value);
+        self.arena[idx].soft_deadline = now.saturating_add(soft_ttl);
+        self.move_deadline(idx, now.saturating_add(hard_ttl));
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Refresh-Ahead (Reload Hot Entries Before They Expire)
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Turns on refresh-ahead: `maintenance` (so also the Daemon) reloads,
+    /// through the refresher, every entry with less than `window` left to
+    /// live whose counter is above the average, and restarts it with `ttl`.
+    /// Hot entries are replaced in place and never miss at expiry; cold ones
+    /// expire as usual. Needs `set_refresher`; reloads run without the main
+    /// lock, but on the thread calling `maintenance`.
+    pub fn set_refresh_ahead(&self, window: Duration, ttl: impl Into<Ttl>) {
+        let window = u64::try_from(window.as_millis()).unwrap_or(u64::MAX);
+        self.main.lock().refresh_ahead = Some((window, ttl.into()));
+    }
+
+    pub fn clear_refresh_ahead(&self) {
+        self.main.lock().refresh_ahead = None;
+    }
+
+    // Helper: Reloads the refresh-ahead candidates; returns how many were replaced
+    pub(crate) fn refresh_ahead(&self) -> usize {
+        let (candidates, refresher, ttl) = {
+            let guard = self.main.lock();
+            let (Some((window, ttl)), Some(refresher)) = (guard.refresh_ahead, guard.refresher.clone()) else {
+                return 0;
+            };
+            (guard.refresh_ahead_candidates(window), refresher, ttl)
+        };
+
+        let mut refreshed = 0;
+        for key in candidates {
+            if let Some(value) = refresher(&key) {
+                self.write(|cache| cache.apply_refresh_ahead(&key, value, ttl));
+                refreshed += 1;
+            }
+        }
+        refreshed
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Live, unexpired, unpinned keys due within `window` and hotter than average
+    fn refresh_ahead_candidates(&self, window: u64) -> Vec<K> {
+        let live = self.live_len();
+        if live == 0 {
+            return Vec::new();
+        }
+        let now = self.current_time();
+        let average = self.counter_sum / live as u64;
+        (0..self.arena.len())
+            .filter(|&i| {
+                let node = &self.arena[i];
+                !node.pinned
+                    && node.time_stamp != u64::MAX
+                    && !node.is_expired(now)
+                    && node.time_stamp - now <= window
+                    && self.frequency(i) > average
+                    && self.is_live(i)
+            })
+            .map(|i| self.arena[i].key.clone())
+            .collect()
+    }
+
+    // Helper: Swaps in the reloaded value and restarts its deadlines from now
+    fn apply_refresh_ahead(&mut self, key: &K, value: V, ttl: Ttl) {
+        let Some(idx) = self.position(key) else { return };
+        let now = self.current_time();
+        self.update_value(key, value);
+        let node = &mut self.arena[idx];
+        if node.soft_ttl > 0 {
+            node.soft_deadline = now.saturating_add(node.soft_ttl);
+        }
+        self.move_deadline(idx, ttl.deadline(now));
+    }
+}
@@ -0,0 +1,326 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+// -----------------------------------------------------------------------------
+// Structural Sharing Containers (Copy-on-Write Segments)
+// -----------------------------------------------------------------------------
+//
+// `sync_mirror` publishes `main` by cloning it. With these containers a clone
+// only copies the outer `Vec` of `Arc`s; the first write to a segment after a
+// commit pays for copying that one segment (`Arc::make_mut`), everything else
+// stays shared with the mirror.
+
+/// Nodes per arena segment.
+const SEGMENT_LEN: usize = 1_024;
+/// Target keys per index shard (shard count is derived from capacity).
+const SHARD_TARGET: usize = 1_024;
+/// Upper bound on index shards, keeps a commit's outer copy small.
+const MAX_SHARDS: usize = 4_096;
+
+/// Segments detached from a `SegmentedVec` (see `split_off_segments`).
+pub(crate) type Segments<T> = Vec<Arc<Vec<T>>>;
+
+/// A `Vec<T>` split into fixed-size `Arc` segments.
+/// Invariant: every segment but the last is full; the last is never empty.
+#[derive(Clone, Debug)]
+pub(crate) struct SegmentedVec<T> {
+    segments: Vec<Arc<Vec<T>>>,
+    len: usize,
+}
+
+impl<T: Clone> SegmentedVec<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Option<&T> {
+        if idx >= self.len {
+            return None;
+        }
+        Some(&self.segments[idx / SEGMENT_LEN][idx % SEGMENT_LEN])
+    }
+
+    pub(crate) fn push(&mut self, value: T) {
+        if self.len.is_multiple_of(SEGMENT_LEN) {
+            self.segments.push(Arc::new(Vec::with_capacity(SEGMENT_LEN)));
+        }
+        let last = self.segments.last_mut().expect("segment just ensured");
+        Arc::make_mut(last).push(value);
+        self.len += 1;
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let last = self.segments.last_mut()?;
+        let value = Arc::make_mut(last).pop();
+        if last.is_empty() {
+            self.segments.pop();
+        }
+        self.len -= 1;
+        value
+    }
+
+    pub(crate) fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.len && b < self.len, "swap index out of bounds");
+        let (seg_a, off_a) = (a / SEGMENT_LEN, a % SEGMENT_LEN);
+        let (seg_b, off_b) = (b / SEGMENT_LEN, b % SEGMENT_LEN);
+
+        if seg_a == seg_b {
+            Arc::make_mut(&mut self.segments[seg_a]).swap(off_a, off_b);
+            return;
+        }
+
+        // Borrow both segments mutably without cloning the elements
+        let (lo, hi, off_lo, off_hi) = if seg_a < seg_b {
+            (seg_a, seg_b, off_a, off_b)
+        } else {
+            (seg_b, seg_a, off_b, off_a)
+        };
+        let (left, right) = self.segments.split_at_mut(hi);
+        std::mem::swap(
+            &mut Arc::make_mut(&mut left[lo])[off_lo],
+            &mut Arc::make_mut(&mut right[0])[off_hi],
+        );
+    }
+
+    /// Removes `[at, len)` as whole segments, without cloning elements of
+    /// segments still shared with a mirror (only the one containing `at` is
+    /// split). For handing the tail off to be dropped elsewhere.
+    pub(crate) fn split_off_segments(&mut self, at: usize) -> Segments<T> {
+        if at >= self.len {
+            return Vec::new();
+        }
+        let mut removed = self.segments.split_off(at / SEGMENT_LEN);
+
+        // The segment containing `at` keeps its head
+        let offset = at % SEGMENT_LEN;
+        if offset > 0 {
+            let mut head = removed.remove(0);
+            let tail = Arc::make_mut(&mut head).split_off(offset);
+            self.segments.push(head);
+            removed.insert(0, Arc::new(tail));
+        }
+
+        self.len = at;
+        removed
+    }
+
+    pub(crate) fn swap_remove(&mut self, idx: usize) -> T {
+        let last = self.len - 1;
+        self.swap(idx, last);
+        self.pop().expect("non-empty after bounds check")
+    }
+}
+
+impl<T: Clone> Index<usize> for SegmentedVec<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        self.get(idx).expect("arena index out of bounds")
+    }
+}
+
+impl<T: Clone> IndexMut<usize> for SegmentedVec<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        assert!(idx < self.len, "arena index out of bounds");
+        let segment = &mut self.segments[idx / SEGMENT_LEN];
+        &mut Arc::make_mut(segment)[idx % SEGMENT_LEN]
+    }
+}
+
+/// A `HashMap<K, usize, S>` split into `Arc` shards selected by key hash.
+#[derive(Clone, Debug)]
+pub(crate) struct ShardedIndex<K, S = RandomState> {
+    shards: Vec<Arc<HashMap<K, usize, S>>>,
+    hasher: S,
+    len: usize,
+}
+
+impl<K: Hash + Eq + Clone, S: BuildHasher + Clone> ShardedIndex<K, S> {
+    pub(crate) fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let count = (capacity / SHARD_TARGET).next_power_of_two().clamp(1, MAX_SHARDS);
+        let per_shard = capacity / count;
+        Self {
+            shards: (0..count)
+                .map(|_| Arc::new(HashMap::with_capacity_and_hasher(per_shard, hasher.clone())))
+                .collect(),
+            hasher,
+            len: 0,
+        }
+    }
+
+    fn shard_of<Q>(&self, key: &Q) -> usize
+    where
+        Q: Hash + ?Sized,
+    {
+        // Shard count is a power of two. The shards hash with the same builder,
+        // so take the high half: the low bits pick the bucket inside a shard.
+        ((self.hasher.hash_one(key) >> 32) as usize) & (self.shards.len() - 1)
+    }
+
+    // Borrow guarantees `hash(q) == hash(k)`, so borrowed lookups hit the same shard
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<&usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shards[self.shard_of(key)].get(key)
+    }
+
+    pub(crate) fn insert(&mut self, key: K, idx: usize) -> Option<usize> {
+        let shard = self.shard_of(&key);
+        let prev = Arc::make_mut(&mut self.shards[shard]).insert(key, idx);
+        if prev.is_none() {
+            self.len += 1;
+        }
+        prev
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &usize)> {
+        self.shards.iter().flat_map(|shard| shard.iter())
+    }
+
+    /// Keeps only the entries for which `keep` holds; returns how many were
+    /// removed. Shards without a rejected entry are not copied.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&K, usize) -> bool) -> usize {
+        let mut removed = 0;
+        for shard in &mut self.shards {
+            if shard.iter().all(|(key, &idx)| keep(key, idx)) {
+                continue;
+            }
+            let map = Arc::make_mut(shard);
+            let before = map.len();
+            map.retain(|key, idx| keep(key, *idx));
+            removed += before - map.len();
+        }
+        self.len -= removed;
+        removed
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for shard in &mut self.shards {
+            *shard = Arc::new(HashMap::with_hasher(self.hasher.clone()));
+        }
+        self.len = 0;
+    }
+
+    pub(crate) fn remove<Q>(&mut self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let shard = self.shard_of(key);
+        // Avoid copying a shared shard when the key is not there
+        if !self.shards[shard].contains_key(key) {
+            return None;
+        }
+        let prev = Arc::make_mut(&mut self.shards[shard]).remove(key);
+        if prev.is_some() {
+            self.len -= 1;
+        }
+        prev
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(len: usize) -> SegmentedVec<usize> {
+        let mut vec = SegmentedVec::new();
+        for i in 0..len {
+            vec.push(i);
+        }
+        vec
+    }
+
+    #[test]
+    fn split_off_segments_keeps_head_and_shares_whole_segments() {
+        let mut vec = filled(2 * SEGMENT_LEN + 500);
+        let mirror = vec.clone();
+        let at = SEGMENT_LEN + 476;
+
+        let removed = vec.split_off_segments(at);
+        assert_eq!(vec.len(), at);
+        assert_eq!(removed.iter().map(|seg| seg.len()).sum::<usize>(), mirror.len() - at);
+        assert_eq!(removed[0][0], at);
+        assert!(Arc::ptr_eq(&removed[1], &mirror.segments[2]));
+        assert!((0..at).all(|i| vec[i] == i));
+
+        // The mirror still sees every element
+        assert_eq!(mirror.len(), 2 * SEGMENT_LEN + 500);
+        assert!((0..mirror.len()).all(|i| mirror[i] == i));
+
+        vec.push(usize::MAX);
+        assert_eq!(vec[at], usize::MAX);
+        assert!(vec.split_off_segments(vec.len()).is_empty());
+    }
+
+    #[test]
+    fn split_off_segments_on_boundary() {
+        let mut vec = filled(2 * SEGMENT_LEN);
+        let removed = vec.split_off_segments(SEGMENT_LEN);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(vec.len(), SEGMENT_LEN);
+        assert_eq!(vec.segments.len(), 1);
+    }
+
+    #[test]
+    fn swap_across_segments_copies_only_written_segments() {
+        let mut vec = filled(3 * SEGMENT_LEN);
+        let mirror = vec.clone();
+        let far = 2 * SEGMENT_LEN + 7;
+
+        vec.swap(5, far);
+        assert_eq!((vec[5], vec[far]), (far, 5));
+        vec.swap(far, 5);
+        assert_eq!((vec[5], vec[far]), (5, far));
+        assert_eq!((mirror[5], mirror[far]), (5, far));
+        assert!(Arc::ptr_eq(&vec.segments[1], &mirror.segments[1]));
+
+        assert_eq!(vec.swap_remove(3), 3);
+        assert_eq!(vec[3], 3 * SEGMENT_LEN - 1);
+        assert_eq!(vec.len(), 3 * SEGMENT_LEN - 1);
+    }
+
+    #[test]
+    fn retain_removes_rejected_and_skips_untouched_shards() {
+        let mut index = ShardedIndex::with_capacity_and_hasher(8 * SHARD_TARGET, RandomState::new());
+        for key in 0..5_000usize {
+            index.insert(key, key);
+        }
+
+        let mirror = index.clone();
+        assert_eq!(index.retain(|_, _| true), 0);
+        assert!(index.shards.iter().zip(&mirror.shards).all(|(a, b)| Arc::ptr_eq(a, b)));
+
+        assert_eq!(index.retain(|_, idx| idx % 2 == 0), 2_500);
+        assert_eq!(index.len(), 2_500);
+        assert_eq!(index.get(&4), Some(&4));
+        assert_eq!(index.get(&5), None);
+        assert_eq!(mirror.get(&5), Some(&5));
+        assert_eq!(index.iter().count(), 2_500);
+    }
+}
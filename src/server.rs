@@ -0,0 +1,254 @@
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::{DualCache, Ttl};
+
+// -----------------------------------------------------------------------------
+// Redis-Subset Server (feature = "server")
+// -----------------------------------------------------------------------------
+
+type ByteCache = DualCache<Vec<u8>, Vec<u8>>;
+
+// Same limits as Redis: requests beyond them are protocol errors
+const MAX_ARGS: usize = 1024 * 1024;
+const MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+
+/// Serves a `DualCache<Vec<u8>, Vec<u8>>` over TCP with a subset of the Redis
+/// protocol (RESP2, plus inline commands for telnet), so redis-cli and
+/// Redis clients can use it as a sidecar cache:
+///
+/// - `GET key`, `SET key value [EX secs | PX millis]`, `DEL key [key ...]`
+/// - `TTL key` (-2 missing, -1 no expiry), `INFO`, `PING [msg]`, `QUIT`
+///
+/// `SET` without `EX` / `PX` never expires (the membrane still evicts).
+/// Reads go to the mirror, so a write shows up in `GET` at the next commit:
+/// run a Daemon or pick a `CommitPolicy`. One thread per connection.
+/// The `dualcache-server` binary wraps this with a Daemon.
+#[derive(Clone)]
+pub struct RespServer {
+    cache: Arc<ByteCache>,
+}
+
+// One reply, written by `write_reply`
+enum Reply {
+    Simple(&'static str),
+    Error(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    Nil,
+}
+
+impl RespServer {
+    pub fn new(cache: Arc<ByteCache>) -> Self {
+        Self { cache }
+    }
+
+    pub fn cache(&self) -> &Arc<ByteCache> {
+        &self.cache
+    }
+
+    /// Accepts connections until `listener` fails, each on its own thread.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let server = self.clone();
+            thread::Builder::new()
+                .name("dualcache-conn".into())
+                .spawn(move || server.serve_stream(stream))?;
+        }
+        Ok(())
+    }
+
+    // Helper: Runs one TCP connection; its errors only end that connection
+    fn serve_stream(&self, stream: TcpStream) {
+        let _ = stream.set_nodelay(true);
+        let Ok(read_half) = stream.try_clone() else { return };
+        let _ = self.serve_connection(BufReader::new(read_half), BufWriter::new(stream));
+    }
+
+    /// Answers requests from `reader` on `writer` until end of input or `QUIT`.
+    /// Replies are flushed once no pipelined request is buffered.
+    pub fn serve_connection(&self, mut reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        loop {
+            let args = match read_request(&mut reader) {
+                Ok(Some(args)) => args,
+                Ok(None) => return writer.flush(),
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                    // The stream can't be re-synchronized; report and hang up
+                    write_reply(&mut writer, &Reply::Error(format!("ERR Protocol error: {err}")))?;
+                    return writer.flush();
+                }
+                Err(err) => return Err(err),
+            };
+            if args.is_empty() {
+                continue;
+            }
+            let quit = args[0].eq_ignore_ascii_case(b"QUIT");
+            let reply = if quit { Reply::Simple("OK") } else { self.execute(&args) };
+            write_reply(&mut writer, &reply)?;
+            if quit {
+                return writer.flush();
+            }
+            if reader.fill_buf()?.is_empty() {
+                writer.flush()?;
+            }
+        }
+    }
+
+    // Helper: Runs one command against the cache
+    fn execute(&self, args: &[Vec<u8>]) -> Reply {
+        let name = String::from_utf8_lossy(&args[0]).to_ascii_uppercase();
+        match (name.as_str(), &args[1..]) {
+            ("PING", []) => Reply::Simple("PONG"),
+            ("PING", [message]) => Reply::Bulk(message.clone()),
+            ("GET", [key]) => self.cache.get(key).map_or(Reply::Nil, Reply::Bulk),
+            ("SET", [key, value, options @ ..]) => match parse_set_ttl(options) {
+                Ok(ttl) => {
+                    self.cache.insert_with_ttl(key.clone(), value.clone(), ttl);
+                    Reply::Simple("OK")
+                }
+                Err(reply) => reply,
+            },
+            ("DEL", keys) if !keys.is_empty() => {
+                // Counted on `main`, so keys deleted before the next commit are not counted twice
+                let deleted = self.cache.write(|cache| {
+                    keys.iter().filter(|key| cache.double_swap_delete(*key).is_some()).count()
+                });
+                Reply::Integer(deleted as i64)
+            }
+            ("TTL", [key]) => Reply::Integer(match self.cache.get_entry(key) {
+                None => -2,
+                Some(entry) if entry.ttl_remaining == Duration::MAX => -1,
+                // Rounded to the nearest second, as Redis does
+                Some(entry) => ((entry.ttl_remaining.as_millis() + 500) / 1000) as i64,
+            }),
+            ("INFO", [] | [_]) => Reply::Bulk(self.info().into_bytes()),
+            ("PING" | "GET" | "SET" | "DEL" | "TTL" | "INFO", _) => Reply::Error(format!(
+                "ERR wrong number of arguments for '{}' command",
+                name.to_ascii_lowercase()
+            )),
+            _ => Reply::Error(format!("ERR unknown command '{}'", String::from_utf8_lossy(&args[0]))),
+        }
+    }
+
+    // Helper: INFO payload (Redis `field:value` lines)
+    fn info(&self) -> String {
+        let stats = self.cache.stats();
+        format!(
+            "# Server\r\nserver:dualcache\r\nversion:{}\r\n\r\n\
+             # Stats\r\nkeyspace_hits:{}\r\nkeyspace_misses:{}\r\nhit_ratio:{:.4}\r\n\
+             insertions:{}\r\nevicted_keys:{}\r\nexpired_keys:{}\r\nrejected_keys:{}\r\n\
+             dropped_signals:{}\r\n\r\n\
+             # Keyspace\r\nkeys:{}\r\ncapacity:{}\r\nevict_point:{}\r\ngeneration:{}\r\n",
+            env!("CARGO_PKG_VERSION"),
+            stats.hits,
+            stats.misses,
+            stats.hit_ratio(),
+            stats.insertions,
+            stats.evictions,
+            stats.expirations,
+            stats.rejections,
+            stats.dropped_signals,
+            stats.occupancy,
+            stats.capacity,
+            self.cache.evict_point(),
+            self.cache.current_generation(),
+        )
+    }
+}
+
+// Helper: `[EX secs | PX millis]` of a SET; no option means no expiry
+fn parse_set_ttl(options: &[Vec<u8>]) -> Result<Ttl, Reply> {
+    let [unit, amount] = options else {
+        return match options {
+            [] => Ok(Ttl::Never),
+            _ => Err(Reply::Error("ERR syntax error".into())),
+        };
+    };
+    let amount = std::str::from_utf8(amount)
+        .ok()
+        .and_then(|amount| amount.parse::<u64>().ok())
+        .filter(|&amount| amount > 0)
+        .ok_or_else(|| Reply::Error("ERR invalid expire time in 'set' command".into()))?;
+    if unit.eq_ignore_ascii_case(b"EX") {
+        Ok(Ttl::Seconds(amount))
+    } else if unit.eq_ignore_ascii_case(b"PX") {
+        Ok(Ttl::Millis(amount))
+    } else {
+        Err(Reply::Error("ERR syntax error".into()))
+    }
+}
+
+// Helper: Reads one request as its arguments: a RESP array of bulk strings,
+// or an inline (space separated) line. `None` at end of input.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Option<Vec<Vec<u8>>>> {
+    let Some(line) = read_line(reader)? else { return Ok(None) };
+    let Some(count) = line.strip_prefix(b"*") else {
+        let inline = line.split(|byte| byte.is_ascii_whitespace());
+        return Ok(Some(inline.filter(|arg| !arg.is_empty()).map(<[u8]>::to_vec).collect()));
+    };
+
+    let count = parse_len(count, MAX_ARGS)?;
+    let mut args = Vec::with_capacity(count.min(64));
+    for _ in 0..count {
+        let header = read_line(reader)?.ok_or_else(|| protocol_error("unexpected end of input"))?;
+        let len = header
+            .strip_prefix(b"$")
+            .ok_or_else(|| protocol_error("expected '$'"))
+            .and_then(|len| parse_len(len, MAX_BULK_LEN))?;
+        let mut arg = vec![0; len + 2];
+        reader.read_exact(&mut arg)?;
+        if !arg.ends_with(b"\r\n") {
+            return Err(protocol_error("bulk string not terminated by CRLF"));
+        }
+        arg.truncate(len);
+        args.push(arg);
+    }
+    Ok(Some(args))
+}
+
+// Helper: One line without its `\r\n` (or bare `\n`)
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut line = Vec::new();
+    if reader.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(line))
+}
+
+// Helper: Parses a RESP length no larger than `max`
+fn parse_len(digits: &[u8], max: usize) -> io::Result<usize> {
+    std::str::from_utf8(digits)
+        .ok()
+        .and_then(|digits| digits.parse::<usize>().ok())
+        .filter(|&len| len <= max)
+        .ok_or_else(|| protocol_error("invalid length"))
+}
+
+fn protocol_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Helper: Encodes one RESP2 reply
+fn write_reply(writer: &mut impl Write, reply: &Reply) -> io::Result<()> {
+    match reply {
+        Reply::Simple(status) => write!(writer, "+{status}\r\n"),
+        Reply::Error(message) => write!(writer, "-{message}\r\n"),
+        Reply::Integer(value) => write!(writer, ":{value}\r\n"),
+        Reply::Bulk(data) => {
+            write!(writer, "${}\r\n", data.len())?;
+            writer.write_all(data)?;
+            writer.write_all(b"\r\n")
+        }
+        Reply::Nil => writer.write_all(b"$-1\r\n"),
+    }
+}
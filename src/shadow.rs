@@ -0,0 +1,178 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use crate::DualCache;
+use crate::stats::StripedCounter;
+
+// -----------------------------------------------------------------------------
+// Shadow Cache (Online A/B of Eviction Policies)
+// -----------------------------------------------------------------------------
+
+/// Side-by-side read outcomes of a `ShadowDualCache`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShadowReport {
+    pub reads: u64,
+    pub primary_hits: u64,
+    pub shadow_hits: u64,
+    /// Reads the primary served but the shadow would have missed.
+    pub primary_only: u64,
+    /// Reads the primary missed but the shadow would have served.
+    pub shadow_only: u64,
+}
+
+impl ShadowReport {
+    pub fn primary_hit_ratio(&self) -> f64 {
+        ratio(self.primary_hits, self.reads)
+    }
+
+    pub fn shadow_hit_ratio(&self) -> f64 {
+        ratio(self.shadow_hits, self.reads)
+    }
+
+    /// `shadow_hit_ratio - primary_hit_ratio`: positive when the shadow's
+    /// configuration would have done better.
+    pub fn hit_ratio_delta(&self) -> f64 {
+        self.shadow_hit_ratio() - self.primary_hit_ratio()
+    }
+}
+
+// Helper: `part / total`, 0.0 for an empty total
+fn ratio(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    part as f64 / total as f64
+}
+
+/// Serves from `primary` and replays every operation into a key-only
+/// `shadow` cache with another configuration (policy, capacity, membrane),
+/// counting where their hits differ. The shadow never serves a value.
+///
+/// Both caches keep their own signal channel: run a Daemon (or
+/// `maintenance`) for each, so the shadow's membrane evolves as it would
+/// in production.
+pub struct ShadowDualCache<K, V, S, SS = S>
+where
+    K: Hash + Eq + Clone,
+{
+    primary: Arc<DualCache<K, V, S>>,
+    shadow: Arc<DualCache<K, (), SS>>,
+    primary_hits: StripedCounter,
+    shadow_hits: StripedCounter,
+    primary_only: StripedCounter,
+    shadow_only: StripedCounter,
+    reads: StripedCounter,
+}
+
+impl<K, V, S, SS> ShadowDualCache<K, V, S, SS>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+    SS: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Wraps two already built caches. Build the shadow with the configuration
+    /// under test; it only holds keys.
+    pub fn new(primary: Arc<DualCache<K, V, S>>, shadow: Arc<DualCache<K, (), SS>>) -> Self {
+        Self {
+            primary,
+            shadow,
+            primary_hits: StripedCounter::new(),
+            shadow_hits: StripedCounter::new(),
+            primary_only: StripedCounter::new(),
+            shadow_only: StripedCounter::new(),
+            reads: StripedCounter::new(),
+        }
+    }
+
+    pub fn primary(&self) -> &Arc<DualCache<K, V, S>> {
+        &self.primary
+    }
+
+    pub fn shadow(&self) -> &Arc<DualCache<K, (), SS>> {
+        &self.shadow
+    }
+
+    /// Reads the primary; the same lookup (and read signal) goes to the shadow.
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.primary.get(key);
+        let shadow_hit = self.shadow.get(key).is_some();
+
+        self.reads.incr();
+        match (value.is_some(), shadow_hit) {
+            (true, true) => {
+                self.primary_hits.incr();
+                self.shadow_hits.incr();
+            }
+            (true, false) => {
+                self.primary_hits.incr();
+                self.primary_only.incr();
+            }
+            (false, true) => {
+                self.shadow_hits.incr();
+                self.shadow_only.incr();
+            }
+            (false, false) => {}
+        }
+        value
+    }
+
+    pub fn insert(&self, key: K, value: V, ttl_secs: u64) {
+        self.shadow.insert(key.clone(), (), ttl_secs);
+        self.primary.insert(key, value, ttl_secs);
+    }
+
+    /// Inserts with the primary's default TTL (the shadow uses its own).
+    pub fn put(&self, key: K, value: V) {
+        self.shadow.put(key.clone(), ());
+        self.primary.put(key, value);
+    }
+
+    pub fn delete<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shadow.delete(key);
+        self.primary.delete(key);
+    }
+
+    /// Commits both caches; returns the primary's generation.
+    pub fn commit(&self) -> u64 {
+        self.shadow.commit();
+        self.primary.commit()
+    }
+
+    /// Runs `maintenance` on both caches.
+    pub fn maintenance(&self) {
+        self.shadow.maintenance();
+        self.primary.maintenance();
+    }
+
+    /// Comparative counters since construction (or the last `reset_report`).
+    pub fn report(&self) -> ShadowReport {
+        ShadowReport {
+            reads: self.reads.get(),
+            primary_hits: self.primary_hits.get(),
+            shadow_hits: self.shadow_hits.get(),
+            primary_only: self.primary_only.get(),
+            shadow_only: self.shadow_only.get(),
+        }
+    }
+
+    /// Returns the report and starts a fresh measurement window.
+    pub fn reset_report(&self) -> ShadowReport {
+        ShadowReport {
+            reads: self.reads.take(),
+            primary_hits: self.primary_hits.take(),
+            shadow_hits: self.shadow_hits.take(),
+            primary_only: self.primary_only.take(),
+            shadow_only: self.shadow_only.take(),
+        }
+    }
+}
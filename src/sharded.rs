@@ -0,0 +1,206 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use crossbeam::channel::Receiver;
+
+use crate::{CacheStats, DaemonConfig, DaemonHandle, DualCache, DualCacheBuilder, Signal};
+
+// -----------------------------------------------------------------------------
+// Sharded DualCache (Main-Lock Striping)
+// -----------------------------------------------------------------------------
+
+/// Hashes keys across N independent `DualCache` shards, each with its own
+/// `main` lock, mirror, and signal channel.
+pub struct ShardedDualCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    shards: Vec<Arc<DualCache<K, V>>>,
+    hasher: RandomState,
+}
+
+impl<K, V> ShardedDualCache<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Splits `capacity` evenly across `shard_count` shards.
+    /// Returns one receiver per shard, in shard order.
+    pub fn new(capacity: usize, shard_count: usize) -> (Self, Vec<Receiver<Signal<K>>>) {
+        let shard_count = shard_count.max(1);
+        Self::with_builder(shard_count, DualCacheBuilder::new(capacity.div_ceil(shard_count)))
+    }
+
+    /// Builds every shard from `builder` (its capacity is per shard).
+    pub fn with_builder(
+        shard_count: usize,
+        builder: DualCacheBuilder,
+    ) -> (Self, Vec<Receiver<Signal<K>>>) {
+        let (shards, receivers) = (0..shard_count.max(1))
+            .map(|_| builder.clone().build())
+            .unzip();
+
+        let sharded = Self {
+            shards,
+            hasher: RandomState::new(),
+        };
+        (sharded, receivers)
+    }
+
+    /// Spawns one daemon per shard. `receivers` must be in shard order.
+    pub fn spawn_daemons(
+        &self,
+        receivers: Vec<Receiver<Signal<K>>>,
+        config: DaemonConfig,
+    ) -> Vec<DaemonHandle> {
+        self.shards
+            .iter()
+            .zip(receivers)
+            .map(|(shard, rx)| shard.spawn_daemon(rx, config.clone()))
+            .collect()
+    }
+
+    /// Serves the shards with `workers` daemon threads instead of one per shard.
+    /// Every shard keeps its own channel (selected by key hash), and shard `i`
+    /// is owned by worker `i % workers`, so workers apply signals concurrently
+    /// on disjoint key ranges without contending for the same `main` lock.
+    pub fn spawn_daemon_pool(
+        &self,
+        receivers: Vec<Receiver<Signal<K>>>,
+        workers: usize,
+        config: DaemonConfig,
+    ) -> Vec<DaemonHandle> {
+        let workers = workers.clamp(1, self.shards.len());
+        let mut lanes: Vec<Vec<_>> = (0..workers).map(|_| Vec::new()).collect();
+        for (idx, (shard, rx)) in self.shards.iter().zip(receivers).enumerate() {
+            lanes[idx % workers].push(shard.daemon_lane(rx));
+        }
+
+        lanes
+            .into_iter()
+            .map(|lanes| DualCache::spawn_worker("dualcache-daemon", lanes, config.clone()))
+            .collect()
+    }
+
+    /// The shard that owns `key` (borrowed forms hash identically).
+    pub fn shard_for<Q>(&self, key: &Q) -> &Arc<DualCache<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.hasher.hash_one(key) as usize % self.shards.len();
+        &self.shards[idx]
+    }
+
+    pub fn shards(&self) -> &[Arc<DualCache<K, V>>] {
+        &self.shards
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).get(key)
+    }
+
+    pub fn insert(&self, key: K, value: V, ttl_secs: u64) {
+        self.shard_for(&key).insert(key, value, ttl_secs);
+    }
+
+    pub fn put(&self, key: K, value: V) {
+        self.shard_for(&key).put(key, value);
+    }
+
+    pub fn get_or_insert_with<F>(&self, key: K, ttl_secs: u64, init: F) -> V
+    where
+        F: FnOnce() -> V,
+    {
+        self.shard_for(&key).get_or_insert_with(key, ttl_secs, init)
+    }
+
+    pub fn update<Q>(&self, key: &Q, value: V)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).update(key, value);
+    }
+
+    pub fn delete<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).delete(key);
+    }
+
+    pub fn refresh_ttl<Q>(&self, key: &Q, ttl_secs: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).refresh_ttl(key, ttl_secs)
+    }
+
+    pub fn expire_at<Q>(&self, key: &Q, timestamp: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).expire_at(key, timestamp)
+    }
+
+    pub fn insert_tagged<I, T>(&self, key: K, value: V, ttl_secs: u64, tags: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.shard_for(&key).insert_tagged(key, value, ttl_secs, tags);
+    }
+
+    /// Invalidates `tag` on every shard, returning the total removed.
+    pub fn invalidate_tag(&self, tag: &str) -> usize {
+        self.shards.iter().map(|shard| shard.invalidate_tag(tag)).sum()
+    }
+
+    /// Runs `retain` on every shard (each shard commits on its own).
+    pub fn retain(&self, mut keep: impl FnMut(&K, &V) -> bool) -> usize {
+        self.shards.iter().map(|shard| shard.retain(&mut keep)).sum()
+    }
+
+    pub fn invalidate_where(&self, mut predicate: impl FnMut(&K, &V) -> bool) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.invalidate_where(&mut predicate))
+            .sum()
+    }
+
+    pub fn maintenance(&self) {
+        self.shards.iter().for_each(|shard| shard.maintenance());
+    }
+
+    /// Commits every shard. Shards are published one after another,
+    /// so readers may briefly see some shards ahead of others.
+    pub fn commit(&self) {
+        self.shards.iter().for_each(|shard| {
+            shard.commit();
+        });
+    }
+
+    /// Splits `new_capacity` evenly across the shards and resizes each.
+    pub fn resize(&self, new_capacity: usize) {
+        let per_shard = new_capacity.div_ceil(self.shards.len());
+        self.shards.iter().for_each(|shard| shard.resize(per_shard));
+    }
+
+    /// Sum of all shard statistics.
+    pub fn stats(&self) -> CacheStats {
+        self.shards
+            .iter()
+            .map(|shard| shard.stats())
+            .fold(CacheStats::default(), CacheStats::merge)
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{CacheStats, Clock, DualCacheBuilder, LocalDualCache, MockClock};
+
+// -----------------------------------------------------------------------------
+// Trace-Driven Simulation (feature = "simulate")
+// -----------------------------------------------------------------------------
+
+/// One step of an access trace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TraceOp<K> {
+    /// A read. On a miss the key is filled (cache-aside) unless
+    /// `Simulator::fill_on_miss(false)`.
+    Get(K),
+    /// A write with the builder's default TTL.
+    Insert(K),
+    Delete(K),
+    /// Moves the mock clock forward (TTL / TTI expiry).
+    Advance(Duration),
+}
+
+/// Cache state after some prefix of the trace.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationSample {
+    /// Trace operations replayed so far.
+    pub ops: u64,
+    pub clock_millis: u64,
+    pub evict_point: usize,
+    pub len: usize,
+    /// Cumulative cliff-edge evictions.
+    pub evictions: u64,
+    /// Hit ratio of the reads since the previous sample.
+    pub window_hit_ratio: f64,
+}
+
+/// Outcome of `Simulator::run`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SimulationReport {
+    pub ops: u64,
+    pub stats: CacheStats,
+    /// One sample every `sample_every` operations, plus one at the end.
+    pub timeline: Vec<SimulationSample>,
+}
+
+impl SimulationReport {
+    pub fn hit_ratio(&self) -> f64 {
+        self.stats.hit_ratio()
+    }
+
+    /// Total distance the membrane moved between consecutive samples.
+    pub fn membrane_travel(&self) -> usize {
+        self.timeline
+            .windows(2)
+            .map(|pair| pair[0].evict_point.abs_diff(pair[1].evict_point))
+            .sum()
+    }
+}
+
+/// Replays a trace against a `LocalDualCache` on a `MockClock`.
+///
+/// A hit is promoted right away, i.e. as if no read signal were ever
+/// dropped; `maintenance` (and thus the membrane update) runs every
+/// `maintenance_every` operations, standing in for the Daemon's tick.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    builder: DualCacheBuilder,
+    start_secs: u64,
+    maintenance_every: u64,
+    sample_every: u64,
+    fill_on_miss: bool,
+}
+
+impl Simulator {
+    /// Simulates a cache built from `builder`; its clock is replaced by the mock clock.
+    pub fn new(builder: DualCacheBuilder) -> Self {
+        Self {
+            builder,
+            start_secs: 0,
+            maintenance_every: 1_024,
+            sample_every: 1_024,
+            fill_on_miss: true,
+        }
+    }
+
+    /// Initial mock clock reading.
+    pub fn start_secs(mut self, secs: u64) -> Self {
+        self.start_secs = secs;
+        self
+    }
+
+    /// Operations between maintenance passes (0 = never).
+    pub fn maintenance_every(mut self, ops: u64) -> Self {
+        self.maintenance_every = ops;
+        self
+    }
+
+    /// Operations between timeline samples (0 = final sample only).
+    pub fn sample_every(mut self, ops: u64) -> Self {
+        self.sample_every = ops;
+        self
+    }
+
+    /// Whether a missed `Get` inserts the key (default: true).
+    pub fn fill_on_miss(mut self, fill: bool) -> Self {
+        self.fill_on_miss = fill;
+        self
+    }
+
+    pub fn run<K>(&self, trace: impl IntoIterator<Item = TraceOp<K>>) -> SimulationReport
+    where
+        K: Hash + Eq + Clone,
+    {
+        let clock = Arc::new(MockClock::new(self.start_secs));
+        let mut cache: LocalDualCache<K, ()> = self.builder.clone().clock(clock.clone()).build_local();
+        let mut report = SimulationReport::default();
+        let mut window = (0u64, 0u64);
+
+        for op in trace {
+            match op {
+                TraceOp::Get(key) => {
+                    if cache.get(&key).is_some() {
+                        cache.promote(&key);
+                        window.0 += 1;
+                    } else {
+                        window.1 += 1;
+                        if self.fill_on_miss {
+                            cache.put(key, ());
+                        }
+                    }
+                }
+                TraceOp::Insert(key) => cache.put(key, ()),
+                TraceOp::Delete(key) => {
+                    cache.delete(&key);
+                }
+                TraceOp::Advance(by) => clock.advance(by),
+            }
+            report.ops += 1;
+
+            if self.maintenance_every > 0 && report.ops % self.maintenance_every == 0 {
+                cache.maintenance();
+            }
+            if self.sample_every > 0 && report.ops % self.sample_every == 0 {
+                report.timeline.push(sample(&cache, &clock, report.ops, &mut window));
+            }
+        }
+
+        if report.timeline.last().is_none_or(|last| last.ops != report.ops) {
+            report.timeline.push(sample(&cache, &clock, report.ops, &mut window));
+        }
+        report.stats = cache.stats();
+        report
+    }
+}
+
+// Helper: Snapshots the cache and resets the (hits, misses) window
+fn sample<K>(cache: &LocalDualCache<K, ()>, clock: &MockClock, ops: u64, window: &mut (u64, u64)) -> SimulationSample
+where
+    K: Hash + Eq + Clone,
+{
+    let (hits, misses) = std::mem::take(window);
+    SimulationSample {
+        ops,
+        clock_millis: clock.now_millis(),
+        evict_point: cache.evict_point(),
+        len: cache.len(),
+        evictions: cache.stats().evictions,
+        window_hit_ratio: if hits + misses == 0 {
+            0.0
+        } else {
+            hits as f64 / (hits + misses) as f64
+        },
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Reference Policies
+// -----------------------------------------------------------------------------
+
+/// Textbook policies to compare the membrane against on the same trace.
+/// They ignore `Advance` (no expiry) and fill on every miss.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Baseline {
+    /// Evicts the least recently used key.
+    Lru,
+    /// Evicts the least frequently used key (ties: least recent), without aging.
+    Lfu,
+}
+
+impl Baseline {
+    /// Replays `trace` with room for `capacity` keys. Only `hits`, `misses`,
+    /// `insertions`, `evictions`, `occupancy` and `capacity` are filled in.
+    pub fn run<K>(self, capacity: usize, trace: impl IntoIterator<Item = TraceOp<K>>) -> CacheStats
+    where
+        K: Hash + Eq + Clone,
+    {
+        // Ordered by (frequency, last use); LRU keeps the frequency at 0
+        let mut entries: HashMap<K, (u64, u64)> = HashMap::new();
+        let mut order: BTreeMap<(u64, u64), K> = BTreeMap::new();
+        let mut stats = CacheStats {
+            capacity,
+            ..CacheStats::default()
+        };
+        let mut tick = 0u64;
+
+        for op in trace {
+            tick += 1;
+            let key = match op {
+                TraceOp::Get(key) => {
+                    if entries.contains_key(&key) {
+                        stats.hits += 1;
+                    } else {
+                        stats.misses += 1;
+                    }
+                    key
+                }
+                TraceOp::Insert(key) => key,
+                TraceOp::Delete(key) => {
+                    if let Some(rank) = entries.remove(&key) {
+                        order.remove(&rank);
+                    }
+                    continue;
+                }
+                TraceOp::Advance(_) => continue,
+            };
+
+            let frequency = match entries.get(&key) {
+                Some(&rank) => {
+                    order.remove(&rank);
+                    rank.0 + 1
+                }
+                None => {
+                    if capacity == 0 {
+                        continue;
+                    }
+                    if entries.len() >= capacity
+                        && let Some((_, victim)) = order.pop_first()
+                    {
+                        entries.remove(&victim);
+                        stats.evictions += 1;
+                    }
+                    stats.insertions += 1;
+                    1
+                }
+            };
+            let rank = (if self == Self::Lfu { frequency } else { 0 }, tick);
+            entries.insert(key.clone(), rank);
+            order.insert(rank, key);
+        }
+
+        stats.occupancy = entries.len();
+        stats
+    }
+}
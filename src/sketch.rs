@@ -0,0 +1,85 @@
+use std::hash::{BuildHasher, Hash, RandomState};
+
+// -----------------------------------------------------------------------------
+// Frequency Sketch (TinyLFU Admission)
+// -----------------------------------------------------------------------------
+
+const DEPTH: usize = 4;
+const MAX_COUNT: u8 = 15;
+
+// Per-row multipliers (odd, so distinct rows spread the same hash differently)
+const SEEDS: [u64; DEPTH] = [
+    0x9E37_79B9_7F4A_7C15,
+    0xC2B2_AE3D_27D4_EB4F,
+    0x1656_67B1_9E37_79F9,
+    0x85EB_CA77_C2B2_AE63,
+];
+
+/// Count-min sketch of recent key popularity with 4-bit-style saturating
+/// counters. All counters are halved every `sample_size` increments so the
+/// estimate tracks recent traffic rather than all-time totals.
+#[derive(Debug)]
+pub(crate) struct FrequencySketch {
+    table: Vec<u8>,
+    mask: usize,
+    hasher: RandomState,
+    additions: usize,
+    sample_size: usize,
+    // Times the table was halved
+    resets: u64,
+}
+
+impl FrequencySketch {
+    pub(crate) fn new(capacity: usize) -> Self {
+        // ~16 counters per cached entry across the rows, like Caffeine's sketch
+        let width = capacity.saturating_mul(4).max(64).next_power_of_two();
+        Self {
+            table: vec![0; width * DEPTH],
+            mask: width - 1,
+            hasher: RandomState::new(),
+            additions: 0,
+            sample_size: capacity.max(64).saturating_mul(10),
+            resets: 0,
+        }
+    }
+
+    // Helper: Table slot of `hash` in `row`
+    fn slot(&self, hash: u64, row: usize) -> usize {
+        let spread = hash.wrapping_mul(SEEDS[row]);
+        row * (self.mask + 1) + ((spread >> 32) as usize & self.mask)
+    }
+
+    pub(crate) fn increment<Q: Hash + ?Sized>(&mut self, key: &Q, by: u64) {
+        let hash = self.hasher.hash_one(key);
+        let by = by.min(MAX_COUNT as u64) as u8;
+        for row in 0..DEPTH {
+            let slot = self.slot(hash, row);
+            self.table[slot] = self.table[slot].saturating_add(by).min(MAX_COUNT);
+        }
+
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            self.age();
+        }
+    }
+
+    pub(crate) fn estimate<Q: Hash + ?Sized>(&self, key: &Q) -> u8 {
+        let hash = self.hasher.hash_one(key);
+        (0..DEPTH)
+            .map(|row| self.table[self.slot(hash, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Number of resets so far (each one halved every estimate).
+    pub(crate) fn resets(&self) -> u64 {
+        self.resets
+    }
+
+    // Helper: Halves every counter (the TinyLFU reset)
+    fn age(&mut self) {
+        self.table.iter_mut().for_each(|count| *count /= 2);
+        self.additions /= 2;
+        self.resets += 1;
+    }
+}
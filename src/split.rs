@@ -0,0 +1,88 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Built, Cache, DualCache, DualCacheBuilder, Node};
+
+// -----------------------------------------------------------------------------
+// Splitting a Cache (Live Migration)
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Moves every live, unexpired entry matching `split` into a new cache
+    /// (same capacity, default TTL, clock and hasher; other builder options
+    /// are not carried over, see `split_off_with`). Moved entries keep their
+    /// counters, deadlines, tags, pin and priority, and their relative rank
+    /// order, so the new cache starts warm. Both caches are committed before
+    /// this returns; here the moves count as deletes (listeners, op log).
+    pub fn split_off(&self, split: impl FnMut(&K, &V) -> bool) -> Built<K, V, S> {
+        let builder = {
+            let guard = self.main.lock();
+            DualCacheBuilder::new(guard.capacity)
+                .default_ttl(self.default_ttl)
+                .clock(guard.clock.clone())
+        };
+        self.split_off_with(builder, split)
+    }
+
+    /// `split_off` into a cache built from `builder` (with this cache's
+    /// hasher). Past the new capacity the coldest moved entries are dropped.
+    pub fn split_off_with(&self, builder: DualCacheBuilder, mut split: impl FnMut(&K, &V) -> bool) -> Built<K, V, S> {
+        let hasher = self.main.lock().index.hasher().clone();
+        let (target, rx) = builder.build_with_hasher(hasher);
+
+        let moved = {
+            let mut guard = self.main.lock();
+            let now = guard.current_time();
+            let moved: Vec<Node<K, V>> = (0..guard.arena.len())
+                .filter(|&i| {
+                    let node = &guard.arena[i];
+                    guard.is_live(i) && !node.is_expired(now) && split(&node.key, &node.value)
+                })
+                .map(|i| guard.counted_clone(i))
+                .collect();
+            for node in &moved {
+                guard.double_swap_delete(&node.key);
+            }
+            self.publish(&mut guard);
+            moved
+        };
+
+        let mut guard = target.main.lock();
+        guard.adopt(moved);
+        target.publish(&mut guard);
+        drop(guard);
+        (target, rx)
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Appends `nodes` (hottest first) below the current arena as they
+    // are, up to capacity
+    pub(crate) fn adopt(&mut self, nodes: Vec<Node<K, V>>) {
+        for mut node in nodes {
+            if self.arena.len() >= self.capacity {
+                break;
+            }
+            self.absorb_counter(&mut node);
+            self.total_weight += self.weigh(&node);
+            self.pinned_count += usize::from(node.pinned);
+            self.has_idle_entries |= node.tti > 0;
+            self.has_priorities |= node.priority != crate::Priority::Normal;
+            self.index.insert(node.key.clone(), self.arena.len());
+            self.arena.push(node);
+            let idx = self.arena.len() - 1;
+            self.counter_sum = self.counter_sum.saturating_add(self.frequency(idx));
+            self.schedule_expiry(idx);
+        }
+        self.dirty = true;
+    }
+}
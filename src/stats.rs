@@ -0,0 +1,151 @@
+use std::cell::Cell;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use crossbeam::utils::CachePadded;
+
+// -----------------------------------------------------------------------------
+// Statistics
+// -----------------------------------------------------------------------------
+
+/// Point-in-time counters returned by `DualCache::stats()`.
+/// Read-path counters are relaxed and may lag by a few events.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub insertions: u64,
+    /// Nodes discarded by cliff-edge truncation.
+    pub evictions: u64,
+    /// Nodes invalidated because their TTL passed.
+    pub expirations: u64,
+    /// New keys refused by the admission filter.
+    pub rejections: u64,
+    /// Read signals lost because the channel was full (lossy signaling), or
+    /// because the main lock was busy under `SignalPolicy::Inline`.
+    pub dropped_signals: u64,
+    /// Read signals queued for the Daemon when the snapshot was taken.
+    pub channel_len: usize,
+    /// Bound of the signal channel (see `DualCacheBuilder::channel_capacity`).
+    pub channel_capacity: usize,
+    /// Live nodes in `main`.
+    pub occupancy: usize,
+    pub capacity: usize,
+    /// Total weight of live nodes (0 unless a weigher is set).
+    pub total_weight: u64,
+}
+
+impl CacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+
+    /// Fraction of the signal channel currently in use (0.0 when unbounded).
+    pub fn channel_fill_ratio(&self) -> f64 {
+        if self.channel_capacity == 0 {
+            return 0.0;
+        }
+        self.channel_len as f64 / self.channel_capacity as f64
+    }
+
+    // Helper: Field-wise sum, used to aggregate shards
+    pub(crate) fn merge(self, other: Self) -> Self {
+        Self {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            insertions: self.insertions + other.insertions,
+            evictions: self.evictions + other.evictions,
+            expirations: self.expirations + other.expirations,
+            rejections: self.rejections + other.rejections,
+            dropped_signals: self.dropped_signals + other.dropped_signals,
+            channel_len: self.channel_len + other.channel_len,
+            channel_capacity: self.channel_capacity + other.channel_capacity,
+            occupancy: self.occupancy + other.occupancy,
+            capacity: self.capacity + other.capacity,
+            total_weight: self.total_weight + other.total_weight,
+        }
+    }
+}
+
+pub(crate) const STRIPES: usize = 16;
+
+static NEXT_STRIPE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static STRIPE: Cell<usize> = Cell::new(NEXT_STRIPE.fetch_add(1, Ordering::Relaxed) % STRIPES);
+}
+
+thread_local! {
+    // xorshift64 state; seeded per thread, never zero
+    static SAMPLE_STATE: Cell<u64> = Cell::new(RandomState::new().hash_one(NEXT_STRIPE.load(Ordering::Relaxed)) | 1);
+}
+
+// Helper: True with probability 1 / `one_in` (always true for 0 or 1)
+pub(crate) fn sample(one_in: u32) -> bool {
+    one_in <= 1 || random().is_multiple_of(u64::from(one_in))
+}
+
+// Helper: Next value of the calling thread's xorshift64 generator
+pub(crate) fn random() -> u64 {
+    SAMPLE_STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        x
+    })
+}
+
+// Helper: Stripe assigned to the calling thread (round-robin on first use)
+pub(crate) fn current_stripe() -> usize {
+    STRIPE.with(Cell::get)
+}
+
+/// Relaxed counter striped across cache lines so concurrent readers
+/// don't bounce a single line between cores.
+pub(crate) struct StripedCounter {
+    stripes: [CachePadded<AtomicU64>; STRIPES],
+}
+
+impl StripedCounter {
+    pub(crate) fn new() -> Self {
+        Self {
+            stripes: std::array::from_fn(|_| CachePadded::new(AtomicU64::new(0))),
+        }
+    }
+
+    pub(crate) fn incr(&self) {
+        self.stripes[current_stripe()].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> u64 {
+        self.stripes.iter().map(|s| s.load(Ordering::Relaxed)).sum()
+    }
+
+    // Helper: Reads and zeroes the counter (increments racing the reset land in either window)
+    pub(crate) fn take(&self) -> u64 {
+        self.stripes.iter().map(|s| s.swap(0, Ordering::Relaxed)).sum()
+    }
+}
+
+/// Counters touched by the lock-free read path.
+pub(crate) struct ReadCounters {
+    pub(crate) hits: StripedCounter,
+    pub(crate) misses: StripedCounter,
+    pub(crate) dropped_signals: StripedCounter,
+}
+
+impl ReadCounters {
+    pub(crate) fn new() -> Self {
+        Self {
+            hits: StripedCounter::new(),
+            misses: StripedCounter::new(),
+            dropped_signals: StripedCounter::new(),
+        }
+    }
+}
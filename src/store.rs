@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::daemon::spawn_periodic;
+use crate::{DaemonHandle, DualCache, Loader, LoadingDualCache};
+
+// -----------------------------------------------------------------------------
+// Backing Store (Write-Through / Write-Back)
+// -----------------------------------------------------------------------------
+
+/// The system of record behind a `StoredDualCache` (e.g. a database table).
+pub trait BackingStore<K, V>: Send + Sync {
+    fn load(&self, key: &K) -> Option<V>;
+    fn store(&self, key: &K, value: &V);
+    fn remove(&self, key: &K);
+}
+
+/// When writes reach the `BackingStore`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Every insert/delete is stored synchronously before returning.
+    #[default]
+    WriteThrough,
+    /// Writes are buffered as dirty entries and stored by `flush`
+    /// (or a `spawn_flusher` thread). Dirty entries outlive eviction.
+    WriteBack,
+}
+
+// Adapts a BackingStore to the read-through Loader
+struct StoreLoader<S>(Arc<S>);
+
+impl<K, V, S> Loader<K, V> for StoreLoader<S>
+where
+    S: BackingStore<K, V>,
+{
+    fn load(&self, key: &K) -> Option<V> {
+        self.0.load(key)
+    }
+}
+
+/// A `DualCache` layered in front of a `BackingStore`: misses read through
+/// (one load per key at a time), writes go to the store per `WriteMode`.
+pub struct StoredDualCache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+{
+    loading: LoadingDualCache<K, V, StoreLoader<S>>,
+    store: Arc<S>,
+    mode: WriteMode,
+    ttl_secs: u64,
+    // Pending write-back operations: Some = store, None = remove
+    dirty: Arc<Mutex<HashMap<K, Option<V>>>>,
+}
+
+impl<K, V, S> StoredDualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BackingStore<K, V> + 'static,
+{
+    /// Cached entries (inserted or loaded) use `ttl_secs`.
+    pub fn new(cache: Arc<DualCache<K, V>>, store: Arc<S>, mode: WriteMode, ttl_secs: u64) -> Self {
+        Self {
+            loading: LoadingDualCache::new(cache, StoreLoader(Arc::clone(&store)), ttl_secs),
+            store,
+            mode,
+            ttl_secs,
+            dirty: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Reloads hot entries from the store before they expire (see
+    /// `DualCache::set_refresh_ahead`), restarting them with this cache's
+    /// TTL. Installs the store as the cache's refresher; keys with an
+    /// unflushed write-back are left alone.
+    pub fn refresh_ahead(&self, window: Duration) {
+        let (store, dirty) = (Arc::clone(&self.store), Arc::clone(&self.dirty));
+        self.cache().set_refresher(Arc::new(move |key: &K| {
+            if dirty.lock().contains_key(key) {
+                return None;
+            }
+            store.load(key)
+        }));
+        self.cache().set_refresh_ahead(window, self.ttl_secs);
+    }
+
+    pub fn cache(&self) -> &Arc<DualCache<K, V>> {
+        self.loading.cache()
+    }
+
+    pub fn store(&self) -> &Arc<S> {
+        &self.store
+    }
+
+    /// Cached value, else the pending write-back value, else the store.
+    pub fn get(&self, key: &K) -> Option<V> {
+        if let Some(value) = self.cache().get(key) {
+            return Some(value);
+        }
+        // An unflushed write is newer than whatever the store holds
+        if let Some(pending) = self.dirty.lock().get(key).cloned() {
+            let value = pending?;
+            self.cache().insert(key.clone(), value.clone(), self.ttl_secs);
+            return Some(value);
+        }
+        self.loading.get(key)
+    }
+
+    pub fn insert(&self, key: K, value: V) {
+        match self.mode {
+            WriteMode::WriteThrough => self.store.store(&key, &value),
+            WriteMode::WriteBack => {
+                self.dirty.lock().insert(key.clone(), Some(value.clone()));
+            }
+        }
+        self.cache().insert(key, value, self.ttl_secs);
+    }
+
+    pub fn delete(&self, key: &K) {
+        match self.mode {
+            WriteMode::WriteThrough => self.store.remove(key),
+            WriteMode::WriteBack => {
+                self.dirty.lock().insert(key.clone(), None);
+            }
+        }
+        self.cache().delete(key);
+    }
+
+    /// Writes every dirty entry to the store. Returns the number flushed.
+    /// A no-op in write-through mode.
+    pub fn flush(&self) -> usize {
+        let pending: Vec<(K, Option<V>)> = self.dirty.lock().drain().collect();
+        for (key, value) in &pending {
+            match value {
+                Some(value) => self.store.store(key, value),
+                None => self.store.remove(key),
+            }
+        }
+        pending.len()
+    }
+
+    /// Number of writes not yet flushed to the store.
+    pub fn dirty_len(&self) -> usize {
+        self.dirty.lock().len()
+    }
+
+    /// Flushes write-back entries every `interval` on a background thread.
+    /// `shutdown` on the handle runs one final flush.
+    pub fn spawn_flusher(self: &Arc<Self>, interval: Duration) -> DaemonHandle {
+        let this = Arc::clone(self);
+        spawn_periodic("dualcache-flusher", interval, move || {
+            this.flush();
+            true
+        })
+    }
+}
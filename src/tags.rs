@@ -0,0 +1,41 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Tag-Based Invalidation
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Inserts with a set of tags for `invalidate_tag`.
+    /// Unlike `insert`, an existing key also has its tags replaced.
+    pub fn insert_tagged<I, T>(&self, key: K, value: V, ttl_secs: u64, tags: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+
+        self.write(|cache| {
+            if let Some(idx) = cache.position(&key) {
+                cache.arena[idx].tags = tags;
+                cache.update_value(&key, value);
+                return;
+            }
+            let mut node = cache.new_node(key, value, ttl_secs);
+            node.tags = tags;
+            cache.gatsby_insert_node(node);
+        });
+    }
+
+    /// Deletes every live entry carrying `tag` in one pass over the arena.
+    /// Returns the number of entries removed; visible after the next commit.
+    pub fn invalidate_tag(&self, tag: &str) -> usize {
+        self.write(|cache| cache.delete_where(|node| node.tags.iter().any(|t| t == tag)))
+    }
+}
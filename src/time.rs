@@ -0,0 +1,11 @@
+// -----------------------------------------------------------------------------
+// Platform Time (feature = "wasm")
+// -----------------------------------------------------------------------------
+
+// `std::time::{Instant, SystemTime}` panic on wasm32-unknown-unknown; the
+// `wasm` feature swaps in `web-time`, which reads the JS clock there and
+// re-exports `std` everywhere else.
+#[cfg(not(feature = "wasm"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(feature = "wasm")]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
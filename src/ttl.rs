@@ -0,0 +1,133 @@
+use std::borrow::Borrow;
+use std::hash::{BuildHasher, Hash};
+use std::time::Duration;
+
+use crate::{Cache, DualCache, secs_to_millis};
+
+// -----------------------------------------------------------------------------
+// Per-Key TTL Control (Write Path)
+// -----------------------------------------------------------------------------
+
+/// Lifetime of an entry. Plain `u64` TTLs convert to `Ttl::Seconds`,
+/// `Duration`s to `Ttl::Millis`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ttl {
+    /// Expires this many seconds after the write. `Seconds(0)` is already
+    /// expired on the next check.
+    Seconds(u64),
+    /// Expires this many milliseconds after the write.
+    Millis(u64),
+    /// Never expires; the entry lives until deleted or evicted.
+    Never,
+}
+
+impl Ttl {
+    /// Absolute deadline (milliseconds) for an entry written at `now_millis`.
+    pub fn deadline(self, now_millis: u64) -> u64 {
+        match self {
+            Self::Seconds(secs) => now_millis.saturating_add(secs_to_millis(secs)),
+            Self::Millis(millis) => now_millis.saturating_add(millis),
+            Self::Never => u64::MAX,
+        }
+    }
+}
+
+impl From<u64> for Ttl {
+    fn from(secs: u64) -> Self {
+        Self::Seconds(secs)
+    }
+}
+
+impl From<Duration> for Ttl {
+    fn from(ttl: Duration) -> Self {
+        Self::Millis(u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX))
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Inserts with an explicit `Ttl`.
+    /// Unlike `insert`, an existing key also has its deadline replaced.
+    pub fn insert_with_ttl(&self, key: K, value: V, ttl: impl Into<Ttl>) {
+        let ttl = ttl.into();
+        self.write(|cache| {
+            if let Some(idx) = cache.position(&key) {
+                cache.move_deadline(idx, ttl.deadline(cache.current_time()));
+                cache.update_value(&key, value);
+                return;
+            }
+            let mut node = cache.new_node(key, value, 0);
+            node.time_stamp = ttl.deadline(node.inserted_at);
+            cache.gatsby_insert_node(node);
+        });
+    }
+
+    /// Inserts an entry that never expires (configuration-style data).
+    /// Unlike `insert_pinned` it can still be evicted by the cliff edge.
+    pub fn insert_forever(&self, key: K, value: V) {
+        self.insert_with_ttl(key, value, Ttl::Never);
+    }
+
+    /// Moves `key`'s deadline to `now + ttl_secs` and restarts its idle window
+    /// (session keepalive). Counter and rank are kept.
+    /// Returns false if the key is absent.
+    pub fn refresh_ttl<Q>(&self, key: &Q, ttl_secs: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            let now = cache.current_time();
+            cache.set_deadline(key, now.saturating_add(secs_to_millis(ttl_secs)), Some(now))
+        })
+    }
+
+    /// Sets `key`'s deadline to the absolute clock time `timestamp` (seconds;
+    /// see `expire_at_millis`).
+    /// A past timestamp force-expires the entry without deleting it: readers
+    /// miss once the change is committed and the Daemon reclaims the slot.
+    /// Returns false if the key is absent.
+    pub fn expire_at<Q>(&self, key: &Q, timestamp: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.expire_at_millis(key, secs_to_millis(timestamp))
+    }
+
+    /// `expire_at` with a millisecond timestamp.
+    pub fn expire_at_millis<Q>(&self, key: &Q, timestamp: u64) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| cache.set_deadline(key, timestamp, None))
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Rewrites a live node's deadline in place, optionally touching it
+    fn set_deadline<Q>(&mut self, key: &Q, deadline: u64, touched_at: Option<u64>) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(idx) = self.position(key) else {
+            return false;
+        };
+        self.move_deadline(idx, deadline);
+        if let Some(now) = touched_at {
+            self.arena[idx].last_access = now;
+        }
+        true
+    }
+}
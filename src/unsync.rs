@@ -0,0 +1,214 @@
+use std::borrow::Borrow;
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{Cache, CacheStats, DualCacheBuilder, EvictPointPolicy, secs_to_millis};
+
+// -----------------------------------------------------------------------------
+// Single-Threaded Variant (No Mirror, No Channel)
+// -----------------------------------------------------------------------------
+
+/// `DualCache` without the concurrency machinery: one cache, no main lock,
+/// no mirror, no signal channel. Same viscous climb, Gatsby insert, cliff
+/// edge and membrane, driven by direct calls: reads never promote, and
+/// `promote` applies what a read signal would. Writes are visible at once.
+///
+/// Deterministic given a deterministic clock (see `DualCacheBuilder::clock`),
+/// which makes it suitable for benchmarks, simulations and thread-less targets.
+pub struct LocalDualCache<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Cache<K, V, S>,
+    default_ttl: u64,
+    // Read outcomes fed to the membrane, as `ReadCounters` does for `DualCache`
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl DualCacheBuilder {
+    /// Like `build`, but returns a single-threaded `LocalDualCache`.
+    /// Channel, signal, coalescing, commit and replica settings do not apply.
+    pub fn build_local<K, V>(self) -> LocalDualCache<K, V>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+    {
+        self.build_local_with_hasher(RandomState::new())
+    }
+
+    /// `build_local` with a custom index hasher (see `build_with_hasher`).
+    pub fn build_local_with_hasher<K, V, S>(self, hasher: S) -> LocalDualCache<K, V, S>
+    where
+        K: Hash + Eq + Clone,
+        V: Clone,
+        S: BuildHasher + Clone,
+    {
+        LocalDualCache {
+            cache: self.new_cache(hasher),
+            default_ttl: self.default_ttl,
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+}
+
+impl<K, V> LocalDualCache<K, V>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        DualCacheBuilder::new(capacity).build_local()
+    }
+}
+
+impl<K, V, S> LocalDualCache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    /// Borrows the value if the key is live and unexpired. Does not promote.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self
+            .cache
+            .position(key)
+            .map(|idx| &self.cache.arena[idx])
+            .filter(|node| !node.is_expired(self.cache.current_time()));
+        let counter = if node.is_some() { &self.hits } else { &self.misses };
+        counter.set(counter.get() + 1);
+        node.map(|node| &node.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache
+            .position(key)
+            .is_some_and(|idx| !self.cache.arena[idx].is_expired(self.cache.current_time()))
+    }
+
+    /// Applies one read signal: bumps the counter and climbs one step
+    /// (an expired entry is invalidated instead). Returns whether the key was live.
+    pub fn promote<Q>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Some(idx) = self.cache.position(key) else { return false };
+        let key = self.cache.arena[idx].key.clone();
+        self.cache.viscous_climb(key);
+        true
+    }
+
+    pub fn insert(&mut self, key: K, value: V, ttl_secs: u64) {
+        self.cache.gatsby_insert(key, value, ttl_secs);
+    }
+
+    /// Inserts with the builder's default TTL.
+    pub fn put(&mut self, key: K, value: V) {
+        self.insert(key, value, self.default_ttl);
+    }
+
+    /// See `DualCache::insert_with_tti`.
+    pub fn insert_with_tti(&mut self, key: K, value: V, ttl_secs: u64, tti_secs: u64) {
+        let mut node = self.cache.new_node(key, value, ttl_secs);
+        node.tti = secs_to_millis(tti_secs);
+        self.cache.gatsby_insert_node(node);
+    }
+
+    /// Replaces the value; counter and rank are kept.
+    pub fn update<Q>(&mut self, key: &Q, value: V)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.update_value(key, value);
+    }
+
+    /// Empties the cache, like `DualCache::clear`.
+    pub fn clear(&mut self) -> usize {
+        self.cache.clear()
+    }
+
+    /// Double-swap deletes the key, returning its value.
+    pub fn delete<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.double_swap_delete(key).map(|node| node.value)
+    }
+
+    /// Same steps as `DualCache::maintenance`: wheel and idle expiry, garbage
+    /// sweep, watermark shedding, index purge, counter decay, the membrane
+    /// update, then the memory alarm check.
+    pub fn maintenance(&mut self) {
+        self.cache.expire_due();
+        self.cache.purge_idle();
+        self.cache.sweep_garbage();
+        self.cache.shed_to_low_watermark();
+        self.cache.purge_stale_index();
+        self.cache.decay_counters();
+        self.cache.update_evict_point(self.hits.get(), self.misses.get());
+        self.cache.check_memory_alarm();
+    }
+
+    /// Replaces the membrane dynamics (default: `ViscousMembrane`).
+    pub fn set_evict_point_policy(&mut self, policy: impl EvictPointPolicy + 'static) {
+        self.cache.membrane = Arc::new(Mutex::new(Box::new(policy)));
+    }
+
+    /// Entry count: arena slots minus unlinked garbage.
+    pub fn len(&self) -> usize {
+        self.cache.live_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity
+    }
+
+    /// Current membrane position: slots `[0, evict_point)` are the safe zone.
+    pub fn evict_point(&self) -> usize {
+        self.cache.evict_point
+    }
+
+    /// Same counters as `DualCache::stats`; the channel fields stay 0.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            insertions: self.cache.insertions,
+            evictions: self.cache.evictions,
+            expirations: self.cache.expirations,
+            rejections: self.cache.rejections,
+            occupancy: self.cache.arena.len(),
+            capacity: self.cache.capacity,
+            total_weight: self.cache.total_weight,
+            ..CacheStats::default()
+        }
+    }
+
+    /// Keys in rank order (hottest first), skipping garbage and expired entries.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        let now = self.cache.current_time();
+        (0..self.cache.arena.len())
+            .filter(move |&idx| self.cache.is_live(idx) && !self.cache.arena[idx].is_expired(now))
+            .map(|idx| &self.cache.arena[idx].key)
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache};
+
+// -----------------------------------------------------------------------------
+// Invariant Checking
+// -----------------------------------------------------------------------------
+
+/// One broken invariant of the main cache, found by `DualCache::validate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// `evict_point` lies beyond `capacity`.
+    EvictPointOutOfRange { evict_point: usize, capacity: usize },
+    /// The arena holds more slots than `capacity`.
+    ArenaOverCapacity { len: usize, capacity: usize },
+    /// Several index entries resolve to the same slot (e.g. a key type whose
+    /// `Eq` disagrees with its `Hash`).
+    DuplicateSlot { slot: usize, entries: usize },
+    /// `counter_sum` differs from the sum of the counters in the arena.
+    CounterSumMismatch { recorded: u64, actual: u64 },
+    /// More unlinked slots than the recorded `garbage` bound.
+    GarbageUndercount { recorded: usize, actual: usize },
+    /// The pinned-node count differs from the live pinned nodes.
+    PinnedCountMismatch { recorded: usize, actual: usize },
+    /// With a weigher, the total weight differs from the live nodes' weights.
+    WeightMismatch { recorded: u64, actual: u64 },
+}
+
+/// Result of `DualCache::validate`. Dangling index entries (left behind by
+/// cliff-edge truncation and resolved by lazy validation) are legal, so they
+/// are counted rather than reported as violations.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub arena_len: usize,
+    pub live: usize,
+    pub garbage: usize,
+    pub dangling_index_entries: usize,
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Checks the invariants of `main` under its lock: O(n) over arena and
+    /// index, so meant for tests, debugging, and `debug_validate`.
+    pub fn validate(&self) -> ValidationReport {
+        self.main.lock().validate()
+    }
+
+    /// Panics with the report if `validate` finds a violation.
+    /// Compiled to a no-op without `debug_assertions`.
+    pub fn debug_validate(&self) {
+        if cfg!(debug_assertions) {
+            let report = self.validate();
+            assert!(report.is_ok(), "DualCache invariants violated: {report:?}");
+        }
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport {
+            arena_len: self.arena.len(),
+            ..ValidationReport::default()
+        };
+        let violations = &mut report.violations;
+
+        if self.evict_point > self.capacity {
+            violations.push(Violation::EvictPointOutOfRange {
+                evict_point: self.evict_point,
+                capacity: self.capacity,
+            });
+        }
+        if self.arena.len() > self.capacity {
+            violations.push(Violation::ArenaOverCapacity {
+                len: self.arena.len(),
+                capacity: self.capacity,
+            });
+        }
+
+        // Index side: every entry is either live or dangling
+        let mut entries_per_slot: HashMap<usize, usize> = HashMap::new();
+        for (key, &idx) in self.index.iter() {
+            if idx < self.arena.len() && self.arena[idx].key == *key {
+                *entries_per_slot.entry(idx).or_default() += 1;
+            } else {
+                report.dangling_index_entries += 1;
+            }
+        }
+        let mut duplicates: Vec<(usize, usize)> = entries_per_slot
+            .into_iter()
+            .filter(|&(_, entries)| entries > 1)
+            .collect();
+        duplicates.sort_unstable();
+        violations.extend(
+            duplicates
+                .into_iter()
+                .map(|(slot, entries)| Violation::DuplicateSlot { slot, entries }),
+        );
+
+        // Arena side: counters cover every slot, weights and pins live ones only
+        // (the sketch frequency mode keeps only a running estimate)
+        let counters = self.frequency.is_none().then(|| self.frequency_sum(0..self.arena.len()));
+        let mut weight = 0u64;
+        let mut pinned = 0;
+        for idx in 0..self.arena.len() {
+            let node = &self.arena[idx];
+            if self.is_live(idx) {
+                report.live += 1;
+                weight = weight.saturating_add(self.weigh(node));
+                pinned += usize::from(node.pinned);
+            }
+        }
+        report.garbage = self.arena.len() - report.live;
+
+        if let Some(counters) = counters
+            && counters != self.counter_sum
+        {
+            violations.push(Violation::CounterSumMismatch {
+                recorded: self.counter_sum,
+                actual: counters,
+            });
+        }
+        if report.garbage > self.garbage {
+            violations.push(Violation::GarbageUndercount {
+                recorded: self.garbage,
+                actual: report.garbage,
+            });
+        }
+        if pinned != self.pinned_count {
+            violations.push(Violation::PinnedCountMismatch {
+                recorded: self.pinned_count,
+                actual: pinned,
+            });
+        }
+        if self.weigher.is_some() && weight != self.total_weight {
+            violations.push(Violation::WeightMismatch {
+                recorded: self.total_weight,
+                actual: weight,
+            });
+        }
+        report
+    }
+}
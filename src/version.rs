@@ -0,0 +1,86 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::DualCache;
+
+// -----------------------------------------------------------------------------
+// Versioned Updates (Optimistic Concurrency)
+// -----------------------------------------------------------------------------
+
+/// Why `DualCache::compare_and_update` did not apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CasError {
+    /// The key is absent or expired in `main`.
+    Missing,
+    /// Another update got there first; carries the version now stored.
+    Conflict { current: u64 },
+}
+
+impl fmt::Display for CasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "key is not present in the cache"),
+            Self::Conflict { current } => write!(f, "version conflict (current version {current})"),
+        }
+    }
+}
+
+impl std::error::Error for CasError {}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Like `get`, but also returns the node's version for a later
+    /// `compare_and_update`. Reads the mirror, so the version may lag `main`;
+    /// a stale version simply makes the update fail.
+    pub fn get_versioned<Q>(&self, key: &Q) -> Option<(V, u64)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.read(key, |node| (node.value.clone(), node.version))
+    }
+
+    /// Like `update`, but returns the value it replaced.
+    /// An absent or expired key is left absent and `None` is returned.
+    pub fn replace<Q>(&self, key: &Q, value: V) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            let idx = cache.position(key)?;
+            if cache.arena[idx].is_expired(cache.current_time()) {
+                return None;
+            }
+            let previous = cache.arena[idx].value.clone();
+            cache.update_value(key, value);
+            Some(previous)
+        })
+    }
+
+    /// Updates the value only if the node is still at `expected_version`,
+    /// returning the new version. Counter and rank are kept, as with `update`.
+    pub fn compare_and_update<Q>(&self, key: &Q, expected_version: u64, value: V) -> Result<u64, CasError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.write(|cache| {
+            let idx = cache.position(key).ok_or(CasError::Missing)?;
+            let node = &cache.arena[idx];
+            if node.is_expired(cache.current_time()) {
+                return Err(CasError::Missing);
+            }
+            if node.version != expected_version {
+                return Err(CasError::Conflict { current: node.version });
+            }
+            cache.update_value(key, value);
+            Ok(cache.arena[idx].version)
+        })
+    }
+}
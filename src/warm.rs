@@ -0,0 +1,70 @@
+use std::hash::{BuildHasher, Hash};
+
+use crate::{Cache, DualCache, Node, Priority};
+
+// -----------------------------------------------------------------------------
+// Warm-Up (Preserved Ranking)
+// -----------------------------------------------------------------------------
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Rebuilds the arena from `nodes` in the given rank order (hottest first),
+    /// keeping their counters and timestamps, and commits. In sketch frequency
+    /// mode the counters feed the sketch. Existing entries are discarded
+    /// without listener callbacks; nodes past `capacity` and repeated keys
+    /// are skipped. `evict_point` is left where it was.
+    pub fn warm_from<I>(&self, nodes: I)
+    where
+        I: IntoIterator<Item = Node<K, V>>,
+    {
+        let mut guard = self.main.lock();
+        guard.restore(nodes);
+        self.publish(&mut guard);
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: Replaces the contents with `nodes` in rank order, recomputing
+    // the derived bookkeeping (counter sum, weight, pinned / idle tracking)
+    pub(crate) fn restore(&mut self, nodes: impl IntoIterator<Item = Node<K, V>>) {
+        self.drop_tail(0);
+        self.index.clear();
+        self.counter_sum = 0;
+        self.total_weight = 0;
+        self.pinned_count = 0;
+        self.garbage = 0;
+        self.free.clear();
+        self.dirty = true;
+
+        for mut node in nodes {
+            if self.arena.len() == self.capacity {
+                break;
+            }
+            if self.position(&node.key).is_some() {
+                continue;
+            }
+            let idx = self.arena.len();
+            self.absorb_counter(&mut node);
+            self.total_weight += self.weigh(&node);
+            self.pinned_count += usize::from(node.pinned);
+            self.has_idle_entries |= node.tti > 0;
+            self.has_priorities |= node.priority != Priority::Normal;
+            self.index.insert(node.key.clone(), idx);
+            self.arena.push(node);
+            self.counter_sum = self.counter_sum.saturating_add(self.frequency(idx));
+        }
+        if let Some(wheel) = &self.expiry {
+            wheel.lock().clear();
+            (0..self.arena.len()).for_each(|idx| self.schedule_expiry(idx));
+        }
+    }
+}
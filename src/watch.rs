@@ -0,0 +1,214 @@
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossbeam::channel::{Receiver, Sender, unbounded};
+use parking_lot::Mutex;
+
+use crate::{Cache, CacheEvent, DualCache};
+
+// -----------------------------------------------------------------------------
+// Commit Watch (Generation Notifications)
+// -----------------------------------------------------------------------------
+
+/// Follows published generations without polling: `changed` blocks until a
+/// commit newer than the last one seen. Like a watch channel, only the latest
+/// generation is reported; commits in between are coalesced.
+///
+/// Also an endless blocking iterator: `for generation in cache.watch_commits()`.
+pub struct CommitWatch<K, V, S = RandomState>
+where
+    K: Hash + Eq + Clone,
+{
+    cache: Arc<DualCache<K, V, S>>,
+    seen: u64,
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// A `CommitWatch` that has seen the current generation, so its first
+    /// `changed` returns with the next commit.
+    pub fn watch_commits(self: &Arc<Self>) -> CommitWatch<K, V, S> {
+        CommitWatch {
+            cache: Arc::clone(self),
+            seen: *self.committed.lock(),
+        }
+    }
+
+    /// A `tokio::sync::watch` receiver holding the latest published generation,
+    /// updated by every commit.
+    #[cfg(feature = "async")]
+    pub fn watch_commits_async(&self) -> tokio::sync::watch::Receiver<u64> {
+        self.commit_watch.subscribe()
+    }
+}
+
+impl<K, V, S> CommitWatch<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Last generation returned by `changed` (or current at creation).
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Whether a newer generation was published since `seen`. Does not mark it seen.
+    pub fn has_changed(&self) -> bool {
+        *self.cache.committed.lock() > self.seen
+    }
+
+    /// Blocks until a generation newer than `seen` is published, marks it
+    /// seen and returns it.
+    pub fn changed(&mut self) -> u64 {
+        self.cache.wait_for_generation(self.seen + 1);
+        self.mark_seen()
+    }
+
+    /// Like `changed`, giving up after `timeout` (`None`).
+    pub fn changed_timeout(&mut self, timeout: Duration) -> Option<u64> {
+        self.cache
+            .wait_for_generation_timeout(self.seen + 1, timeout)
+            .then(|| self.mark_seen())
+    }
+
+    /// Marks the latest generation seen without waiting; returns it.
+    pub fn mark_seen(&mut self) -> u64 {
+        self.seen = *self.cache.committed.lock();
+        self.seen
+    }
+
+    pub fn cache(&self) -> &Arc<DualCache<K, V, S>> {
+        &self.cache
+    }
+}
+
+impl<K, V, S> Iterator for CommitWatch<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        Some(self.changed())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Per-Key Watches
+// -----------------------------------------------------------------------------
+
+// Watched keys and the ones changed since the last commit; shared (not
+// copied) with the mirror like the event bus
+pub(crate) struct KeyWatches<K, V> {
+    state: Mutex<KeyWatchState<K, V>>,
+}
+
+struct KeyWatchState<K, V> {
+    watchers: HashMap<K, Vec<Sender<Option<V>>>>,
+    changed: HashSet<K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> KeyWatches<K, V> {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(KeyWatchState {
+                watchers: HashMap::new(),
+                changed: HashSet::new(),
+            }),
+        }
+    }
+
+    // Helper: Records that a watched key changed in `main`
+    pub(crate) fn record(&self, event: &CacheEvent<K>) {
+        let (CacheEvent::Inserted(key)
+        | CacheEvent::Updated(key)
+        | CacheEvent::Evicted(key)
+        | CacheEvent::Expired(key)
+        | CacheEvent::Deleted(key)) = event
+        else {
+            return;
+        };
+        let mut state = self.state.lock();
+        if state.watchers.contains_key(key) {
+            state.changed.insert(key.clone());
+        }
+    }
+
+    // Helper: Delivers what `snapshot` now shows for every changed key,
+    // forgetting watchers whose receiver is gone
+    pub(crate) fn publish<S: BuildHasher + Clone>(&self, snapshot: &Cache<K, V, S>) {
+        let mut state = self.state.lock();
+        if state.changed.is_empty() {
+            return;
+        }
+        let KeyWatchState { watchers, changed } = &mut *state;
+        for key in changed.drain() {
+            let Some(senders) = watchers.get_mut(&key) else { continue };
+            let value = snapshot.visible_value(&key);
+            senders.retain(|sender| sender.send(value.clone()).is_ok());
+            if senders.is_empty() {
+                watchers.remove(&key);
+            }
+        }
+    }
+}
+
+impl<K, V, S> DualCache<K, V, S>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+    S: BuildHasher + Clone + Send + Sync + 'static,
+{
+    /// Streams `key`'s committed value: first what readers see now, then the
+    /// new value (or `None` once deleted, evicted or expired) after every
+    /// commit that changed it. Several writes between two commits arrive as
+    /// one message. The channel is unbounded; drop the receiver to unsubscribe
+    /// (it is forgotten on the key's next change).
+    pub fn watch_key(&self, key: K) -> Receiver<Option<V>> {
+        let (tx, rx) = unbounded();
+        let mut guard = self.main.lock();
+        // Commits publish under the main lock, so no change slips in between
+        let _ = tx.send(self.mirror.load().visible_value(&key));
+        guard
+            .watches
+            .get_or_insert_with(|| Arc::new(KeyWatches::new()))
+            .state
+            .lock()
+            .watchers
+            .entry(key)
+            .or_default()
+            .push(tx);
+        rx
+    }
+}
+
+impl<K, V, S> Cache<K, V, S>
+where
+    K: Hash + Eq + Clone,
+    V: Clone,
+    S: BuildHasher + Clone,
+{
+    // Helper: The value a reader of this snapshot gets (live and unexpired)
+    fn visible_value<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let now = self.current_time();
+        self.position(key)
+            .map(|idx| &self.arena[idx])
+            .filter(|node| !node.is_expired(now))
+            .map(|node| node.value.clone())
+    }
+}